@@ -4,16 +4,249 @@
 
 #include <stddef.h>
 
-#define CPPBIND_CLASS(n) \
-    template<> const size_t cppbind::type_info<n>::size = sizeof(n); \
-    template<> const size_t cppbind::type_info<n>::align = alignof(n)
+#include <cxxabi.h>
+
+#include <cstddef>
+#include <exception>
+#include <functional>
+#include <memory>
+#include <new>
+#include <type_traits>
+#include <typeinfo>
+#include <utility>
+
+// Without aligned new the operator new only guarantee the alignment of the fundamental types.
+#ifdef __cpp_aligned_new
+#define CPPBIND_NEW_ALIGN __STDCPP_DEFAULT_NEW_ALIGNMENT__
+#define CPPBIND_ALIGNED_NEW true
+#else
+#define CPPBIND_NEW_ALIGN alignof(std::max_align_t)
+#define CPPBIND_ALIGNED_NEW false
+#endif
+
+#ifdef __cpp_sized_deallocation
+#define CPPBIND_SIZED_DELETE true
+#else
+#define CPPBIND_SIZED_DELETE false
+#endif
+
+#define CPPBIND_CLASS(...) \
+    template<> void cppbind::type_info<__VA_ARGS__>::deleter(void *p) \
+    { \
+        cppbind::detail::delete_object<__VA_ARGS__>(p, std::has_virtual_destructor<__VA_ARGS__>()); \
+    } \
+    template<> const size_t cppbind::type_info<__VA_ARGS__>::size = sizeof(__VA_ARGS__); \
+    template<> const size_t cppbind::type_info<__VA_ARGS__>::align = alignof(__VA_ARGS__); \
+    template<> const bool cppbind::type_info<__VA_ARGS__>::trivially_copyable = \
+        std::is_trivially_copyable<__VA_ARGS__>::value; \
+    template<> const bool cppbind::type_info<__VA_ARGS__>::trivially_move_constructible = \
+        std::is_trivially_move_constructible<__VA_ARGS__>::value; \
+    template<> const bool cppbind::type_info<__VA_ARGS__>::trivially_destructible = \
+        std::is_trivially_destructible<__VA_ARGS__>::value; \
+    template<> const bool cppbind::type_info<__VA_ARGS__>::trivial_for_calls = \
+        cppbind::detail::trivial_for_calls<__VA_ARGS__>::value; \
+    template<> const size_t cppbind::type_info<__VA_ARGS__>::new_align = CPPBIND_NEW_ALIGN; \
+    template<> const bool cppbind::type_info<__VA_ARGS__>::aligned_new = CPPBIND_ALIGNED_NEW; \
+    template<> const bool cppbind::type_info<__VA_ARGS__>::sized_delete = CPPBIND_SIZED_DELETE; \
+    template<> const bool cppbind::type_info<__VA_ARGS__>::polymorphic = \
+        std::is_polymorphic<__VA_ARGS__>::value; \
+    template<> const bool cppbind::type_info<__VA_ARGS__>::virtual_destructor = \
+        std::has_virtual_destructor<__VA_ARGS__>::value
+
+// The derived class cannot contains a comma so use a type alias if it is a template.
+#define CPPBIND_BASE(derived, ...) \
+    template<> const size_t cppbind::base_info<derived, __VA_ARGS__>::offset = \
+        reinterpret_cast<size_t>(static_cast<__VA_ARGS__ *>(reinterpret_cast<derived *>(alignof(derived)))) - \
+        alignof(derived)
+
+// Offset of a virtual base is only known at runtime so we need the compiler to generate a code to
+// lookup the offset from the vtable.
+#define CPPBIND_VIRTUAL_BASE(derived, ...) \
+    template<> void *cppbind::base_info<derived, __VA_ARGS__>::cast(void *p) \
+    { \
+        return static_cast<__VA_ARGS__ *>(static_cast<derived *>(p)); \
+    }
+
+// Export the vtable slot of a virtual method as a pointer-to-member. The class cannot contains a
+// comma so use a type alias if it is a template.
+#define CPPBIND_VIRTUAL(cls, name) \
+    namespace cppbind { namespace method { struct name; } } \
+    template<> struct cppbind::virtual_info<cls, cppbind::method::name> { \
+        static const decltype(&cls::name) member; \
+    }; \
+    const decltype(&cls::name) cppbind::virtual_info<cls, cppbind::method::name>::member = &cls::name
+
+// Export a thunk that invoke a method inside try/catch so the exception can be returned to Rust. The
+// class cannot contains a comma so use a type alias if it is a template.
+#define CPPBIND_THROWING(cls, name) \
+    namespace cppbind { namespace method { struct name; } } \
+    template<> struct cppbind::throwing_info<cls, cppbind::method::name> { \
+        typedef cppbind::detail::thunk<decltype(&cls::name), &cls::name> type; \
+        static decltype(&type::call) const thunk; \
+    }; \
+    decltype(&cppbind::throwing_info<cls, cppbind::method::name>::type::call) const \
+        cppbind::throwing_info<cls, cppbind::method::name>::thunk = &type::call
+
+// Export the layout of std::function with the specified signature and a shim to construct it from
+// Rust.
+#define CPPBIND_FUNCTION(...) \
+    CPPBIND_CLASS(std::function<__VA_ARGS__>); \
+    template struct cppbind::function_info<__VA_ARGS__>
 
 namespace cppbind {
     template<typename T>
     struct type_info {
         static const size_t size;
         static const size_t align;
+        static const bool trivially_copyable;
+        static const bool trivially_move_constructible;
+        static const bool trivially_destructible;
+        static const bool trivial_for_calls;
+        static const size_t new_align;
+        static const bool aligned_new;
+        static const bool sized_delete;
+        static const bool polymorphic;
+        static const bool virtual_destructor;
+        static void deleter(void *p);
+    };
+
+    namespace detail {
+        // A class is non-trivial for the purposes of calls if it has a non-trivial copy constructor,
+        // move constructor or destructor, or all of its copy and move constructors are deleted. Such
+        // class is passed and returned through a memory provided by the caller.
+        template<typename T>
+        struct trivial_for_calls : std::integral_constant<bool,
+            std::is_trivially_destructible<T>::value &&
+            (std::is_trivially_copy_constructible<T>::value || !std::is_copy_constructible<T>::value) &&
+            (std::is_trivially_move_constructible<T>::value || !std::is_move_constructible<T>::value) &&
+            (std::is_copy_constructible<T>::value || std::is_move_constructible<T>::value)> {
+        };
+
+        // Use delete expression to invoke the destructor of the most derived class.
+        template<typename T>
+        void delete_object(void *p, std::true_type)
+        {
+            delete static_cast<T *>(p);
+        }
+
+        template<typename T>
+        void delete_object(void *, std::false_type)
+        {
+        }
+
+        // Receiver of the exception, which is implemented on Rust side.
+        struct error {
+            void (*set)(error *e, const char *what, const char *type);
+        };
+
+        template<typename R>
+        struct result {
+            template<typename F>
+            static void invoke(void *r, F f)
+            {
+                new (r) R(f());
+            }
+        };
+
+        template<typename R>
+        struct result<R &> {
+            template<typename F>
+            static void invoke(void *r, F f)
+            {
+                *static_cast<R **>(r) = &f();
+            }
+        };
+
+        template<>
+        struct result<void> {
+            template<typename F>
+            static void invoke(void *, F f)
+            {
+                f();
+            }
+        };
+
+        template<typename R, typename F>
+        bool try_invoke(error *e, void *r, F f)
+        {
+            try {
+                result<R>::invoke(r, f);
+                return true;
+            } catch (const std::exception &ex) {
+                e->set(e, ex.what(), typeid(ex).name());
+#ifdef __GLIBCXX__
+            } catch (abi::__forced_unwind &) {
+                // Thread cancellation must not be swallowed otherwise the process will be aborted.
+                throw;
+#endif
+            } catch (...) {
+                auto type = abi::__cxa_current_exception_type();
+                e->set(e, nullptr, type ? type->name() : nullptr);
+            }
+
+            return false;
+        }
+
+        template<typename M, M m>
+        struct thunk;
+
+        template<typename C, typename R, typename... A, R (C::*m)(A...)>
+        struct thunk<R (C::*)(A...), m> {
+            static bool call(error *e, void *r, C *self, A... args)
+            {
+                return try_invoke<R>(e, r, [&]() -> R { return (self->*m)(std::forward<A>(args)...); });
+            }
+        };
+
+        template<typename C, typename R, typename... A, R (C::*m)(A...) const>
+        struct thunk<R (C::*)(A...) const, m> {
+            static bool call(error *e, void *r, const C *self, A... args)
+            {
+                return try_invoke<R>(e, r, [&]() -> R { return (self->*m)(std::forward<A>(args)...); });
+            }
+        };
+    }
+
+    template<typename D, typename B>
+    struct base_info {
+        static const size_t offset;
+        static void *cast(void *p);
+    };
+
+    template<typename C, typename M>
+    struct virtual_info;
+
+    template<typename C, typename M>
+    struct throwing_info;
+
+    template<typename S>
+    struct function_info;
+
+    template<typename R, typename... A>
+    struct function_info<R(A...)> {
+        static void construct(void *p, R (*f)(A..., void *), void *ctx, void (*drop)(void *));
+        static void destroy(void *p);
     };
+
+    // The context is shared between all copies of the std::function and released with the last
+    // one.
+    template<typename R, typename... A>
+    void function_info<R(A...)>::construct(void *p, R (*f)(A..., void *), void *ctx, void (*drop)(void *))
+    {
+        std::shared_ptr<void> owner(ctx, drop);
+
+        new (p) std::function<R(A...)>([f, owner](A... args) -> R {
+            return f(std::forward<A>(args)..., owner.get());
+        });
+    }
+
+    template<typename R, typename... A>
+    void function_info<R(A...)>::destroy(void *p)
+    {
+        typedef std::function<R(A...)> function;
+
+        static_cast<function *>(p)->~function();
+    }
 }
 
 #endif // CPPBIND_HPP_INCLUDED
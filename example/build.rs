@@ -12,6 +12,8 @@ fn main() {
         println!("cargo::rerun-if-changed={}", f.to_str().unwrap());
     }
 
+    println!("cargo::rerun-if-changed=../cppbind.hpp");
+
     b.cpp(true).std("c++14").compile("example");
 
     // Set variables required by cppbind. The CPPBIND_METADATA variable need to be a path to a
@@ -1,19 +1,193 @@
 #include "../../cppbind.hpp"
 
+#include <cstdlib>
+#include <functional>
+#include <stdexcept>
 #include <string>
+#include <utility>
+#include <vector>
 
 class class1 {
 public:
     class1();
     class1(const char *v1);
+    class1(const class1 &other);
+    class1(class1 &&other) noexcept;
     ~class1();
 
     const char *value() const;
+
+    class1 &operator=(const class1 &other);
+    class1 &operator=(class1 &&other) noexcept;
+    bool operator==(const class1 &other) const;
 protected:
     std::string v1;
 };
 
+template<typename T, size_t N>
+class Matrix {
+public:
+    Matrix();
+    ~Matrix();
+
+    T get(int row, int col) const;
+    void set(int row, int col, T v);
+private:
+    T data[N][N];
+};
+
+class Point {
+public:
+    Point(int x, int y);
+
+    int x() const noexcept;
+    int y() const noexcept;
+private:
+    int px;
+    int py;
+};
+
+class Holder {
+public:
+    Holder();
+    ~Holder();
+
+    class1 &item();
+    const class1 &item() const;
+private:
+    class1 v;
+};
+
+class Named {
+public:
+    Named(const char *name);
+    ~Named();
+
+    const char *name() const;
+private:
+    const char *n;
+};
+
+class Dog : public Named {
+public:
+    Dog();
+    virtual ~Dog();
+
+    int legs() const;
+};
+
+class Parser {
+public:
+    Parser();
+    ~Parser();
+
+    int parse(const char *s) const noexcept(false);
+    void fail();
+};
+
+class Button {
+public:
+    Button();
+    ~Button();
+
+    void set_handler(void (*handler)(int, void *), void *context);
+    void click(int times);
+private:
+    void (*h)(int, void *);
+    void *ctx;
+};
+
+class Event {
+public:
+    Event(int code);
+    Event(const Event &other);
+
+    int code() const;
+private:
+    int c;
+};
+
+using EventHandler = std::function<void(const Event &)>;
+
+class Dispatcher {
+public:
+    Dispatcher();
+    ~Dispatcher();
+
+    void add(EventHandler h);
+    void dispatch(int code) const;
+    void dispatch_to(const EventHandler &h, int code) const;
+private:
+    std::vector<EventHandler> handlers;
+};
+
+class Shape {
+public:
+    virtual ~Shape();
+
+    virtual int sides() const = 0;
+    virtual const char *kind() const;
+};
+
+class Square : public Shape {
+public:
+    Square();
+
+    int sides() const override;
+};
+
+// Rust does not know this class.
+class Triangle : public Shape {
+public:
+    static int live;
+
+    Triangle() { live++; }
+    ~Triangle() { live--; }
+
+    int sides() const override { return 3; }
+    const char *kind() const override { return "triangle"; }
+};
+
+int Triangle::live = 0;
+
+class Pooled {
+public:
+    static int live;
+
+    Pooled(int id);
+    virtual ~Pooled();
+
+    static void *operator new(size_t size);
+    static void operator delete(void *ptr, size_t size);
+
+    int id() const;
+private:
+    int i;
+};
+
+int Pooled::live = 0;
+
 CPPBIND_CLASS(class1);
+CPPBIND_CLASS(Matrix<float, 4>);
+CPPBIND_CLASS(Point);
+CPPBIND_CLASS(Holder);
+CPPBIND_CLASS(Named);
+CPPBIND_CLASS(Dog);
+CPPBIND_BASE(Dog, Named);
+CPPBIND_CLASS(Parser);
+CPPBIND_THROWING(Parser, parse);
+CPPBIND_THROWING(Parser, fail);
+CPPBIND_CLASS(Button);
+CPPBIND_CLASS(Event);
+CPPBIND_CLASS(Dispatcher);
+CPPBIND_FUNCTION(void(const Event &));
+CPPBIND_CLASS(Shape);
+CPPBIND_VIRTUAL(Shape, sides);
+CPPBIND_VIRTUAL(Shape, kind);
+CPPBIND_CLASS(Square);
+CPPBIND_BASE(Square, Shape);
+CPPBIND_VIRTUAL(Square, sides);
+CPPBIND_CLASS(Pooled);
 
 class1::class1()
 {
@@ -23,6 +197,14 @@ class1::class1(const char *v1) : v1(v1)
 {
 }
 
+class1::class1(const class1 &other) : v1(other.v1)
+{
+}
+
+class1::class1(class1 &&other) noexcept : v1(std::move(other.v1))
+{
+}
+
 class1::~class1()
 {
 }
@@ -31,3 +213,295 @@ const char *class1::value() const
 {
     return v1.c_str();
 }
+
+class1 &class1::operator=(const class1 &other)
+{
+    v1 = other.v1;
+    return *this;
+}
+
+class1 &class1::operator=(class1 &&other) noexcept
+{
+    v1 = std::move(other.v1);
+    return *this;
+}
+
+bool class1::operator==(const class1 &other) const
+{
+    return v1 == other.v1;
+}
+
+Holder::Holder() : v("held")
+{
+}
+
+Holder::~Holder()
+{
+}
+
+class1 &Holder::item()
+{
+    return v;
+}
+
+const class1 &Holder::item() const
+{
+    return v;
+}
+
+Named::Named(const char *name) : n(name)
+{
+}
+
+Named::~Named()
+{
+}
+
+const char *Named::name() const
+{
+    return n;
+}
+
+Dog::Dog() : Named("dog")
+{
+}
+
+Dog::~Dog()
+{
+}
+
+int Dog::legs() const
+{
+    return 4;
+}
+
+Parser::Parser()
+{
+}
+
+Parser::~Parser()
+{
+}
+
+int Parser::parse(const char *s) const noexcept(false)
+{
+    size_t n;
+    int v = std::stoi(s, &n);
+
+    if (s[n]) {
+        throw std::invalid_argument("trailing characters");
+    }
+
+    return v;
+}
+
+void Parser::fail()
+{
+    throw 42;
+}
+
+Button::Button() : h(nullptr), ctx(nullptr)
+{
+}
+
+Button::~Button()
+{
+}
+
+void Button::set_handler(void (*handler)(int, void *), void *context)
+{
+    h = handler;
+    ctx = context;
+}
+
+void Button::click(int times)
+{
+    if (h) {
+        h(times, ctx);
+    }
+}
+
+Event::Event(int code) : c(code)
+{
+}
+
+Event::Event(const Event &other) : c(other.c)
+{
+}
+
+int Event::code() const
+{
+    return c;
+}
+
+Dispatcher::Dispatcher()
+{
+}
+
+Dispatcher::~Dispatcher()
+{
+}
+
+void Dispatcher::add(EventHandler h)
+{
+    handlers.push_back(std::move(h));
+}
+
+void Dispatcher::dispatch(int code) const
+{
+    Event e(code);
+
+    for (auto &h : handlers) {
+        h(e);
+    }
+}
+
+void Dispatcher::dispatch_to(const EventHandler &h, int code) const
+{
+    h(Event(code));
+}
+
+Shape::~Shape()
+{
+}
+
+const char *Shape::kind() const
+{
+    return "shape";
+}
+
+Square::Square()
+{
+}
+
+int Square::sides() const
+{
+    return 4;
+}
+
+Pooled::Pooled(int id) : i(id)
+{
+}
+
+Pooled::~Pooled()
+{
+}
+
+void *Pooled::operator new(size_t size)
+{
+    live++;
+    return std::malloc(size);
+}
+
+void Pooled::operator delete(void *ptr, size_t)
+{
+    live--;
+    std::free(ptr);
+}
+
+int Pooled::id() const
+{
+    return i;
+}
+
+Point::Point(int x, int y) : px(x), py(y)
+{
+}
+
+int Point::x() const noexcept
+{
+    return px;
+}
+
+int Point::y() const noexcept
+{
+    return py;
+}
+
+template<typename T, size_t N>
+Matrix<T, N>::Matrix() : data()
+{
+}
+
+template<typename T, size_t N>
+Matrix<T, N>::~Matrix()
+{
+}
+
+template<typename T, size_t N>
+T Matrix<T, N>::get(int row, int col) const
+{
+    return data[row][col];
+}
+
+template<typename T, size_t N>
+void Matrix<T, N>::set(int row, int col, T v)
+{
+    data[row][col] = v;
+}
+
+template class Matrix<float, 4>;
+
+template<typename T>
+T max(T a, T b)
+{
+    return a > b ? a : b;
+}
+
+template int max<int>(int, int);
+template double max<double>(double, double);
+
+class1 *new_class1(const char *v)
+{
+    return new class1(v);
+}
+
+void delete_class1(class1 *v)
+{
+    delete v;
+}
+
+class1 *new_class1_array(size_t n)
+{
+    return new class1[n];
+}
+
+size_t count_class1_array(const class1 *v)
+{
+    return reinterpret_cast<const size_t *>(v)[-1];
+}
+
+void delete_class1_array(class1 *v)
+{
+    delete[] v;
+}
+
+void delete_event_array(Event *v)
+{
+    delete[] v;
+}
+
+Shape *new_triangle()
+{
+    return new Triangle();
+}
+
+int live_triangles()
+{
+    return Triangle::live;
+}
+
+void delete_shape(Shape *v)
+{
+    delete v;
+}
+
+int live_pooled()
+{
+    return Pooled::live;
+}
+
+int count_sides(const Shape &v)
+{
+    return v.sides();
+}
@@ -1,20 +1,418 @@
-use cppbind::{cpp, Heap};
+use cppbind::{cpp, Borrowed, Callback, Heap, HeapArray, Inline, Polymorphic};
+use std::ffi::CStr;
+use std::pin::{pin, Pin};
+use std::sync::atomic::{AtomicI32, Ordering};
+use std::sync::Arc;
 
 fn main() {
     // Construct class1 directly on Rust stack.
-    let mut stack = class1_memory::new();
-    let stack = unsafe { class1::new1(&mut stack) };
+    let stack = pin!(class1_memory::new());
+    let stack = unsafe { class1::new1(stack) };
 
     // Construct class1 on C++ heap.
-    let heap = Heap::<class1_memory>::new();
+    let heap = Heap::<class1_memory>::try_new().unwrap();
     let heap = unsafe { class1::new1(heap) };
+
+    println!("{:?}", unsafe { CStr::from_ptr(stack.value()) });
+    println!("{:?}", unsafe { CStr::from_ptr(heap.value()) });
+    println!("{}", stack == heap);
+
+    // Copy class1.
+    let mut copy = unsafe { class1::new2(Heap::new(), c"copy".as_ptr()) };
+
+    println!("{:?}", unsafe { CStr::from_ptr(copy.value()) });
+
+    copy.clone_from(&heap);
+
+    println!("{:?}", unsafe { CStr::from_ptr(copy.clone().value()) });
+
+    // Move class1 from Rust stack to C++ heap.
+    let stack = pin!(class1_memory::new());
+    let stack = unsafe { class1::new2(stack, c"moved".as_ptr()) };
+    let mut moved = unsafe { stack.move_into(Heap::<class1_memory>::new()) };
+
+    println!("{:?}", unsafe { CStr::from_ptr(moved.value()) });
+
+    unsafe { moved.move_assign(copy) };
+
+    println!("{:?}", unsafe { CStr::from_ptr(moved.value()) });
+
+    // Construct a template instantiation.
+    let mut m = unsafe { Matrix_float_4::new1(Heap::<Matrix_float_4Memory>::new()) };
+
+    unsafe { m.set(1, 2, 3.5) };
+
+    println!("{}", unsafe { m.get(1, 2) });
+
+    // Store relocatable objects inline in a Vec.
+    let mut points = Vec::new();
+
+    for i in 0..3 {
+        points.push(unsafe { Point::new1(PointMemory::new(), i, i * 2) });
+    }
+
+    for p in &points {
+        println!("({}, {})", unsafe { p.x() }, unsafe { p.y() });
+    }
+
+    // Embed class1 inside a Rust struct.
+    let mut node = pin!(Node {
+        id: 1,
+        name: unsafe { class1::uninit() },
+    });
+
+    unsafe { node.as_mut().name().init2(c"node".as_ptr()) };
+
+    println!("{} {:?}", node.id, unsafe {
+        CStr::from_ptr(node.name.value())
+    });
+
+    // Borrow class1 owned by C++ object.
+    let mut holder = unsafe { Holder::new1(Heap::<HolderMemory>::new()) };
+    let item = unsafe { holder.item1() };
+
+    println!("{:?}", unsafe { CStr::from_ptr(item.value()) });
+    println!("{:?}", unsafe { CStr::from_ptr(holder.item2().value()) });
+
+    // Transfer ownership to and from C++.
+    let adopted = unsafe { class1::from_raw(new_class1(c"adopted".as_ptr()).cast()) };
+
+    println!("{:?}", unsafe { CStr::from_ptr(adopted.value()) });
+
+    unsafe { delete_class1(adopted.into_raw().cast()) };
+
+    // Allocate an array of class1 and pass it to C++.
+    let array = unsafe { class1::new2_array(3, c"element".as_ptr()) };
+
+    for v in array.iter() {
+        println!("{:?}", unsafe { CStr::from_ptr(v.value()) });
+    }
+
+    let array = array.into_raw();
+
+    println!("{}", unsafe { count_class1_array(array.cast()) });
+
+    unsafe { delete_class1_array(array.cast()) };
+
+    // Adopt an array created by C++.
+    let array = unsafe { HeapArray::<class1_memory>::from_raw(new_class1_array(2).cast()) };
+
+    println!("{}", array.len());
+
+    // Call a method of the base class.
+    let mut dog = unsafe { Dog::new1(Heap::<DogMemory>::new()) };
+
+    println!(
+        "{:?} has {} legs",
+        unsafe { CStr::from_ptr(dog.as_base().name()) },
+        unsafe { dog.legs() }
+    );
+    println!(
+        "{}",
+        dog.as_base().as_ptr() as usize - dog.as_ptr() as usize
+    );
+    println!("{:?}", unsafe { CStr::from_ptr(dog.as_base_mut().name()) });
+
+    // Wrap an object owned by someone else.
+    let named = unsafe { Named::assume_init(Borrowed::new(dog.as_base_mut().as_mut_ptr())) };
+
+    println!("{:?}", unsafe { CStr::from_ptr(named.name()) });
+
+    // Call virtual methods.
+    let square = unsafe { Square::new1(Heap::<SquareMemory>::new()) };
+
+    println!(
+        "{} has {} sides",
+        unsafe { CStr::from_ptr(square.as_base().kind()) }
+            .to_str()
+            .unwrap(),
+        unsafe { square.sides() }
+    );
+    println!("{}", unsafe { square.as_base().sides() });
+
+    let triangle = unsafe { new_triangle() };
+    let shape = unsafe { ShapeRef::from_ptr(triangle.cast()) };
+
+    println!(
+        "{} has {} sides",
+        unsafe { CStr::from_ptr(shape.kind()) }.to_str().unwrap(),
+        unsafe { shape.sides() }
+    );
+
+    println!(
+        "{:?} {}",
+        shape.type_name(),
+        shape.dynamic_cast::<SquareMemory>().is_some()
+    );
+    println!("{:?}", <SquareMemory as Polymorphic>::type_name());
+    println!(
+        "{:?} {}",
+        square.as_base().type_name(),
+        square.as_base().dynamic_cast::<SquareMemory>().is_some()
+    );
+
+    // Destroy the object with its virtual destructor.
+    let triangle = unsafe { Shape::from_raw(triangle.cast()) };
+
+    println!("{}", unsafe { live_triangles() });
+    drop(triangle);
+    println!("{}", unsafe { live_triangles() });
+
+    // Release the memory with the class-specific operator delete.
+    let pooled = unsafe { Pooled::new1(Heap::<PooledMemory>::new(), 7) };
+
+    println!("{} {}", unsafe { pooled.id() }, unsafe { live_pooled() });
+    drop(pooled);
+    println!("{}", unsafe { live_pooled() });
+
+    // Implement an abstract class in Rust.
+    let hexagon = ShapeAdapter::new(Hexagon);
+
+    println!(
+        "{} has {} sides",
+        unsafe { CStr::from_ptr(hexagon.as_base().kind()) }
+            .to_str()
+            .unwrap(),
+        unsafe { count_sides(hexagon.as_ptr().cast()) }
+    );
+
+    unsafe { delete_shape(Box::into_raw(Box::new(hexagon)).cast()) };
+
+    // Catch C++ exceptions.
+    let mut parser = unsafe { Parser::new1(Heap::<ParserMemory>::new()) };
+
+    println!("{:?}", unsafe { parser.parse(c"123".as_ptr()) });
+
+    for v in [c"12a", c"abc"] {
+        println!("{}", unsafe { parser.parse(v.as_ptr()) }.unwrap_err());
+    }
+
+    println!("{}", unsafe { parser.fail() }.unwrap_err());
+
+    // Pass a Rust closure as a pointer to function.
+    let mut button = unsafe { Button::new1(Heap::<ButtonMemory>::new()) };
+    let mut clicks = 0;
+    let mut handler = |n: std::ffi::c_int| clicks += n;
+    let (f, ctx) = unsafe { handler.as_callback() };
+
+    unsafe { button.set_handler(Some(f), ctx) };
+    unsafe { button.click(2) };
+    unsafe { button.click(3) };
+    unsafe { button.set_handler(None, std::ptr::null_mut()) };
+
+    println!("{clicks} clicks");
+
+    // Copy a class that has a trivial destructor but not trivially copyable.
+    let event = unsafe { Event::new1(Heap::<EventMemory>::new(), 4) };
+
+    println!("{}", unsafe { event.clone().code() });
+
+    // Array of a class with a trivial destructor does not have a cookie.
+    let events = unsafe { Event::new1_array(3, 5) };
+
+    println!(
+        "{}",
+        events.iter().map(|e| unsafe { e.code() }).sum::<i32>()
+    );
+
+    unsafe { delete_event_array(events.into_raw().cast()) };
+
+    // Pass a Rust closure as std::function.
+    let total = Arc::new(AtomicI32::new(0));
+    let handler = {
+        let total = total.clone();
+
+        EventHandler::new(move |e| {
+            total.fetch_add(unsafe { e.code() }, Ordering::Relaxed);
+        })
+    };
+
+    let mut dispatcher = unsafe { Dispatcher::new1(Heap::<DispatcherMemory>::new()) };
+
+    unsafe { dispatcher.dispatch_to(&handler, 1) };
+    unsafe { dispatcher.add(handler) };
+    unsafe { dispatcher.dispatch(2) };
+
+    println!(
+        "{} {}",
+        total.load(Ordering::Relaxed),
+        Arc::strong_count(&total)
+    );
+
+    drop(dispatcher);
+
+    println!("{}", Arc::strong_count(&total));
+
+    // Invoke a function template.
+    println!("{}", unsafe { max::<i32>(1, 2) });
+    println!("{}", unsafe { max::<f64>(4.0, 3.0) });
+}
+
+unsafe extern "C-unwind" {
+    #[link_name = "\u{1}_Z10new_class1PKc"]
+    fn new_class1(v: *const std::ffi::c_char) -> *mut ();
+    #[link_name = "\u{1}_Z13delete_class1P6class1"]
+    fn delete_class1(v: *mut ());
+    #[link_name = "\u{1}_Z16new_class1_arraym"]
+    fn new_class1_array(n: usize) -> *mut ();
+    #[link_name = "\u{1}_Z18count_class1_arrayPK6class1"]
+    fn count_class1_array(v: *const ()) -> usize;
+    #[link_name = "\u{1}_Z19delete_class1_arrayP6class1"]
+    fn delete_class1_array(v: *mut ());
+    #[link_name = "\u{1}_Z18delete_event_arrayP5Event"]
+    fn delete_event_array(v: *mut ());
+    #[link_name = "\u{1}_Z12new_trianglev"]
+    fn new_triangle() -> *mut ();
+    #[link_name = "\u{1}_Z14live_trianglesv"]
+    fn live_triangles() -> std::ffi::c_int;
+    #[link_name = "\u{1}_Z11live_pooledv"]
+    fn live_pooled() -> std::ffi::c_int;
+    #[link_name = "\u{1}_Z12delete_shapeP5Shape"]
+    fn delete_shape(v: *mut ());
+    #[link_name = "\u{1}_Z11count_sidesRK5Shape"]
+    fn count_sides(v: *const ()) -> std::ffi::c_int;
+}
+
+struct Hexagon;
+
+impl ShapeImpl for Hexagon {
+    fn sides(&self) -> std::ffi::c_int {
+        6
+    }
+}
+
+impl Drop for Hexagon {
+    fn drop(&mut self) {
+        println!("hexagon dropped");
+    }
+}
+
+struct Node {
+    id: u32,
+    name: class1<Inline<class1_memory>>,
+}
+
+impl Node {
+    fn name(self: Pin<&mut Self>) -> Pin<&mut class1<Inline<class1_memory>>> {
+        unsafe { self.map_unchecked_mut(|v| &mut v.name) }
+    }
 }
 
 cpp! {
     class class1 {
     public:
         class1();
+        class1(const char *v1);
+        class1(const class1 &other);
+        class1(class1 &&other) noexcept;
 
         const char *value() const;
+
+        class1 &operator=(const class1 &other);
+        class1 &operator=(class1 &&other) noexcept;
+        bool operator==(const class1 &other) const;
     };
+
+    class Matrix<float, 4> {
+    public:
+        Matrix();
+
+        float get(int row, int col) const;
+        void set(int row, int col, float v);
+    };
+
+    class [[cppbind::relocatable]] Point {
+    public:
+        Point(int x, int y);
+
+        int x() const noexcept;
+        int y() const noexcept;
+    };
+
+    class Holder {
+    public:
+        Holder();
+
+        class1 &item();
+        const class1 &item() const;
+    };
+
+    class Named {
+    public:
+        Named(const char *name);
+
+        const char *name() const;
+    };
+
+    class Dog : public Named {
+    public:
+        Dog();
+
+        int legs() const;
+    };
+
+    class Parser {
+    public:
+        Parser();
+
+        int parse(const char *s) const noexcept(false);
+        [[cppbind::throws]] void fail();
+    };
+
+    class Button {
+    public:
+        Button();
+
+        void set_handler(void (*handler)(int, void *), void *context);
+        void click(int times);
+    };
+
+    class Event {
+    public:
+        Event(int code);
+        Event(const Event &other);
+
+        int code() const;
+    };
+
+    using EventHandler = std::function<void(const Event &)>;
+
+    class Dispatcher {
+    public:
+        Dispatcher();
+
+        void add(EventHandler h);
+        void dispatch(int code) const;
+        void dispatch_to(const EventHandler &h, int code) const;
+    };
+
+    class Shape {
+    public:
+        virtual ~Shape();
+
+        virtual int sides() const = 0;
+        virtual const char *kind() const;
+    };
+
+    class Square : public Shape {
+    public:
+        Square();
+
+        int sides() const override;
+    };
+
+    class Pooled {
+    public:
+        Pooled(int id);
+        virtual ~Pooled();
+
+        static void *operator new(size_t size);
+        static void operator delete(void *ptr, size_t size);
+
+        int id() const;
+    };
+
+    template<typename T> T max(T a, T b);
+    template<> int max<int>(int, int);
+    template<> double max<double>(double, double);
 }
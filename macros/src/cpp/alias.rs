@@ -0,0 +1,58 @@
+use super::kw;
+use crate::symbol::{Segment, TemplateArg};
+use crate::ty::Type;
+use syn::ext::IdentExt;
+use syn::parse::{Parse, ParseStream};
+use syn::{Error, Ident, Token};
+
+/// C++ alias declaration. Only `std::function` is supported.
+pub struct Alias {
+    pub name: Ident,
+    pub ty: Type,
+}
+
+impl Alias {
+    /// Returns the signature of `std::function`.
+    pub fn signature(&self) -> &Type {
+        match &self.ty {
+            Type::Class(n) => match n.segments() {
+                [_, _, Segment::TemplateArg(TemplateArg::Type(t))] => t,
+                _ => unreachable!(),
+            },
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl Parse for Alias {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        input.parse::<kw::using>()?;
+
+        let name = input.call(Ident::parse_any)?;
+
+        input.parse::<Token![=]>()?;
+
+        // Parse target.
+        let span = input.span();
+        let ty = input.parse::<Type>()?;
+        let valid = match &ty {
+            Type::Class(n) => match n.segments() {
+                [ns, f, Segment::TemplateArg(TemplateArg::Type(t))] => {
+                    *ns == Segment::Ident("std".into())
+                        && *f == Segment::Ident("function".into())
+                        && matches!(t.as_ref(), Type::Func { .. })
+                }
+                _ => false,
+            },
+            _ => false,
+        };
+
+        if !valid {
+            return Err(Error::new(span, "only std::function is supported"));
+        }
+
+        input.parse::<Token![;]>()?;
+
+        Ok(Self { name, ty })
+    }
+}
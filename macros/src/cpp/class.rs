@@ -1,15 +1,20 @@
-use super::func::Param;
+use super::func::{parse_deleted, parse_noexcept, OperatorFunction, Param};
 use super::kw;
-use crate::ty::Type;
+use crate::symbol::Segment;
+use crate::ty::{parse_template_args, Type};
 use proc_macro2::Span;
 use syn::ext::IdentExt;
 use syn::parse::{Parse, ParseStream};
 use syn::punctuated::Punctuated;
-use syn::{braced, parenthesized, Ident, Token};
+use syn::token::{Bracket, Paren};
+use syn::{braced, bracketed, parenthesized, Error, Ident, LitInt, Path, Token};
 
 /// C++ class declaration.
 pub struct Class {
+    pub relocatable: bool,
     pub name: Ident,
+    pub args: Vec<Segment<'static>>,
+    pub bases: Vec<BaseClass>,
     pub ctors: Vec<Ctor>,
     pub members: Vec<Member>,
 }
@@ -19,50 +24,217 @@ impl Parse for Class {
         // Skip declaration.
         input.parse::<kw::class>()?;
 
+        // Parse attributes.
+        let mut relocatable = false;
+
+        while input.peek(Bracket) {
+            let outer;
+            let inner;
+
+            bracketed!(outer in input);
+            bracketed!(inner in outer);
+
+            let path = inner.call(Path::parse_mod_style)?;
+            let names = path.segments.iter().map(|s| &s.ident).collect::<Vec<_>>();
+
+            match names.as_slice() {
+                [ns, name] if *ns == "cppbind" && *name == "relocatable" => relocatable = true,
+                _ => return Err(Error::new_spanned(path, "unknown attribute")),
+            }
+        }
+
         // Parse name.
         let class = input.call(Ident::parse_any)?;
+        let args = if input.peek(Token![<]) {
+            parse_template_args(input)?
+        } else {
+            Vec::new()
+        };
+
+        // Parse base classes.
+        let mut bases = Vec::new();
+
+        if input.peek(Token![:]) {
+            input.parse::<Token![:]>()?;
+
+            loop {
+                bases.push(input.parse::<BaseClass>()?);
+
+                if !input.peek(Token![,]) {
+                    break;
+                }
+
+                input.parse::<Token![,]>()?;
+            }
+        }
+
+        // Parse body.
         let body;
 
         braced!(body in input);
 
-        // Parse body.
         let mut accessibility = Accessibility::Private;
         let mut ctors = Vec::new();
         let mut members = Vec::new();
 
         while !body.is_empty() {
-            let l = body.lookahead1();
-
-            if l.peek(kw::public) {
+            if body.peek(kw::public) {
                 body.parse::<kw::public>().unwrap();
                 body.parse::<Token![:]>()?;
 
                 accessibility = Accessibility::Public;
-            } else if l.peek(Token![const]) {
-                body.parse::<Type>()?;
-            } else if l.peek(Ident) {
-                let r = body.parse::<Ident>()?;
-                let l = body.lookahead1();
-
-                if l.peek(Ident) {
-                    todo!()
-                } else if r == class {
-                    let args;
-
-                    parenthesized!(args in body);
-
-                    ctors.push(Ctor {
-                        access: accessibility,
-                        params: Punctuated::parse_terminated(&args)?,
-                        span: r.span(),
-                    });
-
-                    body.parse::<Token![;]>()?;
-                } else {
-                    return Err(l.error());
-                }
+            } else if body.peek(kw::protected) {
+                body.parse::<kw::protected>().unwrap();
+                body.parse::<Token![:]>()?;
+
+                accessibility = Accessibility::Protected;
+            } else if body.peek(kw::private) {
+                body.parse::<kw::private>().unwrap();
+                body.parse::<Token![:]>()?;
+
+                accessibility = Accessibility::Private;
+            } else if body.peek2(Paren)
+                && body.fork().call(Ident::parse_any).is_ok_and(|v| v == class)
+            {
+                let r = body.call(Ident::parse_any)?;
+                let args;
+
+                parenthesized!(args in body);
+
+                ctors.push(Ctor {
+                    access: accessibility,
+                    params: Punctuated::parse_terminated(&args)?,
+                    span: r.span(),
+                    noexcept: parse_noexcept(&body)? == Some(true),
+                    deleted: parse_deleted(&body)?,
+                });
+
+                body.parse::<Token![;]>()?;
             } else {
-                return Err(l.error());
+                // Parse attributes.
+                let mut throws = None;
+
+                while body.peek(Bracket) {
+                    let outer;
+                    let inner;
+
+                    bracketed!(outer in body);
+                    bracketed!(inner in outer);
+
+                    let path = inner.call(Path::parse_mod_style)?;
+                    let names = path.segments.iter().map(|s| &s.ident).collect::<Vec<_>>();
+
+                    match names.as_slice() {
+                        [ns, name] if *ns == "cppbind" && *name == "throws" => throws = Some(path),
+                        _ => return Err(Error::new_spanned(path, "unknown attribute")),
+                    }
+                }
+
+                // Only allocation functions can be static.
+                let stat = body.parse::<Option<Token![static]>>()?;
+                let virt = body.parse::<Option<Token![virtual]>>()?;
+
+                if let Some(v) = throws.as_ref().filter(|_| !body.peek(Ident::peek_any)) {
+                    return Err(Error::new_spanned(v, "only a method can be throwing"));
+                }
+
+                if stat.is_none() && body.peek(Token![~]) {
+                    members.push(Member::Dtor(Dtor::parse_rest(
+                        &body,
+                        &class,
+                        virt.is_some(),
+                    )?));
+                    continue;
+                }
+
+                let ret = body.parse()?;
+
+                if let Some(v) = throws.as_ref().filter(|_| body.peek(kw::operator)) {
+                    return Err(Error::new_spanned(v, "only a method can be throwing"));
+                } else if body.peek(kw::operator) && (body.peek2(kw::new) || body.peek2(kw::delete))
+                {
+                    members.push(Member::Alloc(AllocFunction::parse_rest(&body, ret)?));
+                    continue;
+                } else if let Some(v) = stat {
+                    return Err(Error::new_spanned(
+                        v,
+                        "static member function is not supported yet",
+                    ));
+                } else if let Some(v) = virt.filter(|_| body.peek(kw::operator)) {
+                    return Err(Error::new_spanned(
+                        v,
+                        "virtual operator is not supported yet",
+                    ));
+                }
+
+                if body.peek(kw::operator) {
+                    let f = OperatorFunction::parse_rest(&body, ret, true)?;
+
+                    members.push(Member::Operator(accessibility, f));
+                    continue;
+                }
+
+                let name = body.call(Ident::parse_any)?;
+                let args;
+
+                parenthesized!(args in body);
+
+                let params = Punctuated::parse_terminated(&args)?;
+                let c = body.parse::<Option<Token![const]>>()?.is_some();
+                let noexcept = parse_noexcept(&body)?;
+
+                if let Some(v) = throws.as_ref().filter(|_| noexcept == Some(true)) {
+                    return Err(Error::new_spanned(v, "noexcept method cannot be throwing"));
+                }
+
+                let throws = throws.is_some() || noexcept == Some(false);
+                let noexcept = noexcept == Some(true);
+                let mut virt = virt.is_some();
+
+                // Parse virt-specifiers, which imply virtual.
+                loop {
+                    if body.peek(Token![override]) {
+                        body.parse::<Token![override]>()?;
+                    } else if body.peek(Token![final]) {
+                        body.parse::<Token![final]>()?;
+                    } else {
+                        break;
+                    }
+
+                    virt = true;
+                }
+
+                // Parse pure-specifier.
+                let pure = if body.peek(Token![=]) && body.peek2(LitInt) {
+                    body.parse::<Token![=]>()?;
+
+                    let v = body.parse::<LitInt>()?;
+
+                    if v.base10_digits() != "0" || !virt {
+                        return Err(Error::new_spanned(v, "invalid pure-specifier"));
+                    }
+
+                    true
+                } else {
+                    false
+                };
+
+                let deleted = parse_deleted(&body)?;
+
+                body.parse::<Token![;]>()?;
+
+                members.push(Member::Method(Method {
+                    access: accessibility,
+                    name,
+                    ret,
+                    params,
+                    c,
+                    virt,
+                    pure,
+                    noexcept,
+                    throws,
+                    deleted,
+                }));
             }
         }
 
@@ -70,17 +242,70 @@ impl Parse for Class {
         input.parse::<Token![;]>()?;
 
         Ok(Self {
+            relocatable,
             name: class,
+            args,
+            bases,
             ctors,
             members,
         })
     }
 }
 
+/// Base class of a C++ class.
+pub struct BaseClass {
+    pub access: Accessibility,
+    pub virt: bool,
+    pub ty: Type,
+    pub span: Span,
+}
+
+impl Parse for BaseClass {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        // The default accessibility of a class is private.
+        let mut access = Accessibility::Private;
+        let mut virt = false;
+
+        loop {
+            if input.peek(kw::public) {
+                input.parse::<kw::public>()?;
+                access = Accessibility::Public;
+            } else if input.peek(kw::protected) {
+                input.parse::<kw::protected>()?;
+                access = Accessibility::Protected;
+            } else if input.peek(kw::private) {
+                input.parse::<kw::private>()?;
+                access = Accessibility::Private;
+            } else if input.peek(Token![virtual]) {
+                input.parse::<Token![virtual]>()?;
+                virt = true;
+            } else {
+                break;
+            }
+        }
+
+        // Parse name.
+        let span = input.span();
+        let ty = input.parse()?;
+
+        if !matches!(ty, Type::Class(_)) {
+            return Err(Error::new(span, "expect a class name"));
+        }
+
+        Ok(Self {
+            access,
+            virt,
+            ty,
+            span,
+        })
+    }
+}
+
 /// Accessibility of a member.
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, PartialEq, Eq)]
 pub enum Accessibility {
     Public,
+    Protected,
     Private,
 }
 
@@ -89,7 +314,230 @@ pub struct Ctor {
     pub access: Accessibility,
     pub params: Punctuated<Param, Token![,]>,
     pub span: Span,
+    pub noexcept: bool,
+    pub deleted: bool,
+}
+
+/// Destructor of a C++ class.
+pub struct Dtor {
+    pub virt: bool,
+    pub noexcept: bool,
+}
+
+impl Dtor {
+    fn parse_rest(input: ParseStream, class: &Ident, mut virt: bool) -> syn::Result<Self> {
+        // Parse name.
+        input.parse::<Token![~]>()?;
+
+        let name = input.call(Ident::parse_any)?;
+
+        if name != *class {
+            return Err(Error::new_spanned(name, "expect the name of the class"));
+        }
+
+        // Parse parameters.
+        let args;
+
+        parenthesized!(args in input);
+
+        if !args.is_empty() {
+            return Err(args.error("destructor cannot have any parameters"));
+        }
+
+        // Destructor is implicitly noexcept unless it is explicitly noexcept(false).
+        let noexcept = parse_noexcept(input)?.unwrap_or(true);
+
+        // Parse virt-specifiers.
+        loop {
+            if input.peek(Token![override]) {
+                input.parse::<Token![override]>()?;
+            } else if input.peek(Token![final]) {
+                input.parse::<Token![final]>()?;
+            } else {
+                break;
+            }
+
+            virt = true;
+        }
+
+        // Parse defaulted definition.
+        if input.peek(Token![=]) {
+            input.parse::<Token![=]>()?;
+            input.parse::<Token![default]>()?;
+        }
+
+        input.parse::<Token![;]>()?;
+
+        Ok(Self { virt, noexcept })
+    }
+}
+
+/// Member of a C++ class (exclude constructor).
+pub enum Member {
+    Method(Method),
+    Operator(Accessibility, OperatorFunction),
+    Alloc(AllocFunction),
+    Dtor(Dtor),
 }
 
-/// Member of a C++ class (exclude constructor and destructor).
-pub enum Member {}
+/// Class-specific `operator new` or `operator delete`.
+pub struct AllocFunction {
+    pub delete: bool,
+    pub span: Span,
+    pub ret: Type,
+    pub params: Punctuated<Param, Token![,]>,
+}
+
+impl AllocFunction {
+    fn parse_rest(input: ParseStream, ret: Type) -> syn::Result<Self> {
+        let span = input.parse::<kw::operator>()?.span;
+        let delete = if input.peek(kw::delete) {
+            input.parse::<kw::delete>()?;
+            true
+        } else {
+            input.parse::<kw::new>()?;
+            false
+        };
+
+        // Parse parameters.
+        let args;
+
+        parenthesized!(args in input);
+
+        let params = Punctuated::parse_terminated(&args)?;
+
+        input.parse::<Token![;]>()?;
+
+        Ok(Self {
+            delete,
+            span,
+            ret,
+            params,
+        })
+    }
+}
+
+/// Non-static member function of a C++ class.
+pub struct Method {
+    pub access: Accessibility,
+    pub name: Ident,
+    pub ret: Type,
+    pub params: Punctuated<Param, Token![,]>,
+    pub c: bool,
+    pub virt: bool,
+    pub pure: bool,
+    pub noexcept: bool,
+    pub throws: bool,
+    pub deleted: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::symbol::Operator;
+
+    fn parse(src: &str) -> syn::Result<Class> {
+        syn::parse_str(src)
+    }
+
+    fn error(src: &str) -> String {
+        match parse(src) {
+            Ok(_) => panic!("unexpected success"),
+            Err(e) => e.to_string(),
+        }
+    }
+
+    #[test]
+    fn members() {
+        let class = parse(
+            "class [[cppbind::relocatable]] Foo : public Bar, virtual Baz {
+            public:
+                Foo(int v) noexcept;
+                virtual ~Foo();
+
+                static void *operator new(size_t size);
+                static void operator delete(void *ptr, size_t size);
+
+                virtual int get() const = 0;
+                int set(int v) override;
+                int parse(const char *s) noexcept(false);
+                [[cppbind::throws]] void fail();
+
+                Foo operator-() const;
+                bool operator!=(const Foo &other) const;
+            private:
+                void hidden();
+            };",
+        )
+        .unwrap();
+
+        assert!(class.relocatable);
+        assert_eq!(class.bases.len(), 2);
+        assert!(class.bases[0].access == Accessibility::Public && !class.bases[0].virt);
+        assert!(class.bases[1].access == Accessibility::Private && class.bases[1].virt);
+        assert_eq!(class.ctors.len(), 1);
+        assert!(class.ctors[0].noexcept);
+
+        let mut iter = class.members.iter();
+
+        assert!(matches!(
+            iter.next(),
+            Some(Member::Dtor(Dtor {
+                virt: true,
+                noexcept: true
+            }))
+        ));
+        assert!(matches!(
+            iter.next(),
+            Some(Member::Alloc(AllocFunction { delete: false, .. }))
+        ));
+        assert!(matches!(
+            iter.next(),
+            Some(Member::Alloc(AllocFunction { delete: true, .. }))
+        ));
+        assert!(matches!(iter.next(), Some(Member::Method(m)) if m.virt && m.pure && m.c));
+        assert!(matches!(iter.next(), Some(Member::Method(m)) if m.virt && !m.pure));
+        assert!(matches!(iter.next(), Some(Member::Method(m)) if m.throws && !m.noexcept));
+        assert!(matches!(iter.next(), Some(Member::Method(m)) if m.throws));
+        assert!(
+            matches!(iter.next(), Some(Member::Operator(_, f)) if f.op == Operator::Neg && f.c)
+        );
+        assert!(matches!(iter.next(), Some(Member::Operator(_, f)) if f.op == Operator::Ne));
+        assert!(
+            matches!(iter.next(), Some(Member::Method(m)) if m.access == Accessibility::Private)
+        );
+        assert!(iter.next().is_none());
+    }
+
+    #[test]
+    fn invalid_members() {
+        assert_eq!(
+            error("class Foo { [[cppbind::throws]] void f() noexcept; };"),
+            "noexcept method cannot be throwing"
+        );
+        assert_eq!(
+            error("class Foo { [[cppbind::throws]] int operator+(int v); };"),
+            "only a method can be throwing"
+        );
+        assert_eq!(
+            error("class Foo { static int f(); };"),
+            "static member function is not supported yet"
+        );
+        assert_eq!(
+            error("class Foo { virtual int operator+(int v); };"),
+            "virtual operator is not supported yet"
+        );
+        assert_eq!(
+            error("class Foo { int f() = 0; };"),
+            "invalid pure-specifier"
+        );
+        assert_eq!(
+            error("class [[cppbind::movable]] Foo {};"),
+            "unknown attribute"
+        );
+        assert_eq!(
+            error("class Foo { ~Bar(); };"),
+            "expect the name of the class"
+        );
+    }
+}
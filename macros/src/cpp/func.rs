@@ -1,10 +1,316 @@
+use super::kw;
+use crate::symbol::{Operator, Segment};
+use crate::ty::{parse_template_args, Type};
+use proc_macro2::Span;
+use syn::ext::IdentExt;
 use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::token::{Bracket, Paren};
+use syn::{bracketed, parenthesized, Error, Ident, LitBool, Token};
 
 /// Parameter of a C++ function/method.
-pub struct Param {}
+pub struct Param {
+    pub ty: Type,
+    pub name: Option<Ident>,
+}
 
 impl Parse for Param {
     fn parse(input: ParseStream) -> syn::Result<Self> {
-        todo!()
+        let (ty, name) = Type::parse_param(input)?;
+        let name = match name {
+            Some(v) => Some(v),
+            None if input.peek(Ident::peek_any) => Some(input.call(Ident::parse_any)?),
+            None => None,
+        };
+
+        Ok(Self { ty, name })
+    }
+}
+
+/// Parses `= delete` at the end of a function declaration.
+pub fn parse_deleted(input: ParseStream) -> syn::Result<bool> {
+    if !input.peek(Token![=]) {
+        return Ok(false);
+    }
+
+    input.parse::<Token![=]>()?;
+    input.parse::<kw::delete>()?;
+
+    Ok(true)
+}
+
+/// Parses an optional noexcept-specifier. Returns [`None`] if the specifier is not present.
+pub fn parse_noexcept(input: ParseStream) -> syn::Result<Option<bool>> {
+    if !input.peek(kw::noexcept) {
+        return Ok(None);
+    }
+
+    input.parse::<kw::noexcept>()?;
+
+    if !input.peek(Paren) {
+        return Ok(Some(true));
+    }
+
+    let arg;
+
+    parenthesized!(arg in input);
+
+    Ok(Some(arg.parse::<LitBool>()?.value))
+}
+
+/// C++ function template or its explicit instantiation.
+pub struct Function {
+    pub template: Vec<Ident>,
+    pub ret: Type,
+    pub name: Ident,
+    pub args: Option<Vec<Segment<'static>>>,
+    pub params: Punctuated<Param, Token![,]>,
+    pub noexcept: bool,
+}
+
+impl Parse for Function {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        // Parse template parameters. Explicit instantiation can be either "template" or
+        // "template<>".
+        let mut template = Vec::new();
+
+        input.parse::<kw::template>()?;
+
+        if input.peek(Token![<]) {
+            input.parse::<Token![<]>()?;
+
+            while !input.peek(Token![>]) {
+                if input.peek(kw::typename) {
+                    input.parse::<kw::typename>()?;
+                } else if input.peek(kw::class) {
+                    input.parse::<kw::class>()?;
+                } else {
+                    input.parse::<Type>()?;
+                }
+
+                template.push(input.call(Ident::parse_any)?);
+
+                if !input.peek(Token![,]) {
+                    break;
+                }
+
+                input.parse::<Token![,]>()?;
+            }
+
+            input.parse::<Token![>]>()?;
+        }
+
+        // Parse declaration.
+        let ret = input.parse()?;
+        let name = input.call(Ident::parse_any)?;
+        let args = if input.peek(Token![<]) {
+            Some(parse_template_args(input)?)
+        } else {
+            None
+        };
+
+        if template.is_empty() == args.is_none() {
+            return Err(Error::new_spanned(
+                name,
+                if template.is_empty() {
+                    "explicit instantiation requires template arguments"
+                } else {
+                    "partial specialization of function template is not allowed"
+                },
+            ));
+        }
+
+        let body;
+
+        parenthesized!(body in input);
+
+        let params = Punctuated::parse_terminated(&body)?;
+        let noexcept = parse_noexcept(input)? == Some(true);
+
+        input.parse::<Token![;]>()?;
+
+        Ok(Self {
+            template,
+            ret,
+            name,
+            args,
+            params,
+            noexcept,
+        })
+    }
+}
+
+/// C++ operator overloading.
+pub struct OperatorFunction {
+    pub op: Operator,
+    pub span: Span,
+    pub ret: Type,
+    pub params: Punctuated<Param, Token![,]>,
+    pub c: bool,
+    pub noexcept: bool,
+    pub deleted: bool,
+}
+
+impl OperatorFunction {
+    /// Parses the declaration after its return type. `member` indicates the declaration is inside a
+    /// class body.
+    pub fn parse_rest(input: ParseStream, ret: Type, member: bool) -> syn::Result<Self> {
+        // Parse operator.
+        let span = input.parse::<kw::operator>()?.span;
+        let op = if input.peek(Token![<<=]) {
+            input.parse::<Token![<<=]>()?;
+            Operator::ShlAssign
+        } else if input.peek(Token![>>=]) {
+            input.parse::<Token![>>=]>()?;
+            Operator::ShrAssign
+        } else if input.peek(Token![<<]) {
+            input.parse::<Token![<<]>()?;
+            Operator::Shl
+        } else if input.peek(Token![>>]) {
+            input.parse::<Token![>>]>()?;
+            Operator::Shr
+        } else if input.peek(Token![<=]) {
+            input.parse::<Token![<=]>()?;
+            Operator::Le
+        } else if input.peek(Token![>=]) {
+            input.parse::<Token![>=]>()?;
+            Operator::Ge
+        } else if input.peek(Token![==]) {
+            input.parse::<Token![==]>()?;
+            Operator::Eq
+        } else if input.peek(Token![!=]) {
+            input.parse::<Token![!=]>()?;
+            Operator::Ne
+        } else if input.peek(Token![+=]) {
+            input.parse::<Token![+=]>()?;
+            Operator::AddAssign
+        } else if input.peek(Token![-=]) {
+            input.parse::<Token![-=]>()?;
+            Operator::SubAssign
+        } else if input.peek(Token![*=]) {
+            input.parse::<Token![*=]>()?;
+            Operator::MulAssign
+        } else if input.peek(Token![/=]) {
+            input.parse::<Token![/=]>()?;
+            Operator::DivAssign
+        } else if input.peek(Token![%=]) {
+            input.parse::<Token![%=]>()?;
+            Operator::RemAssign
+        } else if input.peek(Token![&=]) {
+            input.parse::<Token![&=]>()?;
+            Operator::BitAndAssign
+        } else if input.peek(Token![|=]) {
+            input.parse::<Token![|=]>()?;
+            Operator::BitOrAssign
+        } else if input.peek(Token![^=]) {
+            input.parse::<Token![^=]>()?;
+            Operator::BitXorAssign
+        } else if input.peek(Token![<]) {
+            input.parse::<Token![<]>()?;
+            Operator::Lt
+        } else if input.peek(Token![>]) {
+            input.parse::<Token![>]>()?;
+            Operator::Gt
+        } else if input.peek(Token![+]) {
+            input.parse::<Token![+]>()?;
+            Operator::Add
+        } else if input.peek(Token![-]) {
+            input.parse::<Token![-]>()?;
+            Operator::Sub
+        } else if input.peek(Token![*]) {
+            input.parse::<Token![*]>()?;
+            Operator::Mul
+        } else if input.peek(Token![/]) {
+            input.parse::<Token![/]>()?;
+            Operator::Div
+        } else if input.peek(Token![%]) {
+            input.parse::<Token![%]>()?;
+            Operator::Rem
+        } else if input.peek(Token![&]) {
+            input.parse::<Token![&]>()?;
+            Operator::BitAnd
+        } else if input.peek(Token![|]) {
+            input.parse::<Token![|]>()?;
+            Operator::BitOr
+        } else if input.peek(Token![^]) {
+            input.parse::<Token![^]>()?;
+            Operator::BitXor
+        } else if input.peek(Token![!]) {
+            input.parse::<Token![!]>()?;
+            Operator::Not
+        } else if input.peek(Token![~]) {
+            input.parse::<Token![~]>()?;
+            Operator::BitNot
+        } else if input.peek(Token![=]) {
+            input.parse::<Token![=]>()?;
+            Operator::Assign
+        } else if input.peek(Bracket) {
+            let body;
+
+            bracketed!(body in input);
+
+            if !body.is_empty() {
+                return Err(body.error("expect ']'"));
+            }
+
+            Operator::Index
+        } else if input.peek(Paren) && input.peek2(Paren) {
+            let body;
+
+            parenthesized!(body in input);
+
+            if !body.is_empty() {
+                return Err(body.error("expect ')'"));
+            }
+
+            Operator::Call
+        } else {
+            return Err(input.error("unsupported operator"));
+        };
+
+        // Parse parameters.
+        let body;
+
+        parenthesized!(body in input);
+
+        let params = Punctuated::<Param, Token![,]>::parse_terminated(&body)?;
+        let c = member && input.parse::<Option<Token![const]>>()?.is_some();
+        let noexcept = parse_noexcept(input)? == Some(true);
+        let deleted = parse_deleted(input)?;
+
+        input.parse::<Token![;]>()?;
+
+        // Check number of operands.
+        let operands = params.len() + usize::from(member);
+        let op = match (op, operands) {
+            (Operator::Call, _) if member => op,
+            (op, 1) => op.to_unary().ok_or_else(|| {
+                Error::new(span, format_args!("operator{op} requires two operands"))
+            })?,
+            (op, 2) if !matches!(op, Operator::Not | Operator::BitNot | Operator::Call) => op,
+            (op, _) => {
+                return Err(Error::new(
+                    span,
+                    format_args!("invalid number of operands for operator{op}"),
+                ))
+            }
+        };
+
+        if !member && matches!(op, Operator::Assign | Operator::Index | Operator::Call) {
+            return Err(Error::new(
+                span,
+                format_args!("operator{op} must be a member function"),
+            ));
+        }
+
+        Ok(Self {
+            op,
+            span,
+            ret,
+            params,
+            c,
+            noexcept,
+            deleted,
+        })
     }
 }
@@ -1,198 +1,3539 @@
-use self::class::Class;
-use crate::symbol::{Name, Segment, Signature, Symbol};
+use self::alias::Alias;
+use self::class::{Accessibility, Class, Member};
+use self::func::{Function, OperatorFunction, Param};
+use self::namespace::Namespace;
+use crate::meta::TypeInfo;
+use crate::symbol::{
+    CtorKind, DtorKind, Name, Operator, Segment, Signature, Special, Symbol, TemplateArg,
+};
 use crate::ty::Type;
 use crate::META;
 use proc_macro2::{Literal, Span, TokenStream};
 use quote::{format_ident, quote};
+use std::collections::HashMap;
 use syn::parse::{Parse, ParseStream};
-use syn::Error;
+use syn::{Error, Ident};
 
+mod alias;
 mod class;
 mod func;
+mod namespace;
 
 /// Generates Rust code for `items`.
 pub fn render(items: Declarations) -> syn::Result<TokenStream> {
-    let mut output = TokenStream::new();
+    let mut cx = Context {
+        classes: HashMap::new(),
+        functions: HashMap::new(),
+        aliases: HashMap::new(),
+    };
+
+    cx.collect(&[], &items.0);
+    cx.render_declarations(&[], items.0)
+}
+
+/// Contains information for the whole [cpp](super::cpp) invocation.
+struct Context {
+    classes: HashMap<String, Vec<Ident>>, // Key is a fully qualified name.
+    functions: HashMap<String, Vec<Ident>>, // Key is a fully qualified name of std::function.
+    aliases: HashMap<String, Type>,       // Key is a fully qualified name of the alias.
+}
+
+impl Context {
+    fn collect(&mut self, ns: &[Ident], items: &[Declaration]) {
+        for item in items {
+            match item {
+                Declaration::Class(i) => {
+                    let name = class_name(ns, i).to_string();
+                    let mut path = ns.to_vec();
+
+                    path.push(rust_name(i));
+
+                    self.classes.insert(name, path);
+                }
+                Declaration::Alias(i) => {
+                    let ty = resolve_type(ns, &i.ty);
+                    let mut name = ns
+                        .iter()
+                        .map(|v| Segment::Ident(v.to_string().into()))
+                        .collect::<Vec<_>>();
+                    let mut path = ns.to_vec();
+
+                    name.push(Segment::Ident(i.name.to_string().into()));
+                    path.push(i.name.clone());
+
+                    self.functions.insert(ty.to_string(), path);
+                    self.aliases.insert(Name::Nested(name).to_string(), ty);
+                }
+                Declaration::Function(_) | Declaration::Operator(_) => {}
+                Declaration::Namespace(i) => {
+                    let mut scope = ns.to_vec();
+
+                    scope.extend(i.names.iter().cloned());
+
+                    self.collect(&scope, &i.items.0);
+                }
+            }
+        }
+    }
+
+    fn render_declarations(
+        &self,
+        ns: &[Ident],
+        items: Vec<Declaration>,
+    ) -> syn::Result<TokenStream> {
+        let mut output = TokenStream::new();
+        let mut functions = Vec::new();
+        let mut operators = Vec::new();
+        let mut namespaces = Vec::<(Ident, Vec<Declaration>)>::new();
+
+        for item in items {
+            match item {
+                Declaration::Class(i) => output.extend(self.render_class(ns, i)?),
+                Declaration::Alias(i) => output.extend(self.render_alias(ns, i)?),
+                Declaration::Function(i) => functions.push(i),
+                Declaration::Operator(i) => operators.push(i),
+                Declaration::Namespace(i) => {
+                    // Desugar nested namespace definition.
+                    let mut names = i.names.into_iter();
+                    let name = names.next().unwrap();
+                    let names = names.collect::<Vec<_>>();
+                    let items = if names.is_empty() {
+                        i.items.0
+                    } else {
+                        vec![Declaration::Namespace(Namespace {
+                            names,
+                            items: i.items,
+                        })]
+                    };
+
+                    // Merge with the previous declaration of the same namespace.
+                    match namespaces.iter_mut().find(|(n, _)| *n == name) {
+                        Some((_, v)) => v.extend(items),
+                        None => namespaces.push((name, items)),
+                    }
+                }
+            }
+        }
+
+        output.extend(self.render_functions(ns, functions)?);
+        output.extend(self.render_free_operators(ns, operators)?);
+
+        // Render namespaces as Rust modules.
+        for (name, items) in namespaces {
+            let mut scope = ns.to_vec();
+
+            scope.push(name.clone());
+
+            let items = self.render_declarations(&scope, items)?;
+
+            output.extend(quote! {
+                #[allow(non_snake_case)]
+                pub mod #name {
+                    #items
+                }
+            });
+        }
+
+        Ok(output)
+    }
+
+    fn render_class(&self, ns: &[Ident], item: Class) -> syn::Result<TokenStream> {
+        // Get metadata.
+        let name = class_name(ns, &item).to_string();
+        let class = rust_name(&item);
+        let mem = memory_name(&class);
+        let meta = match META.get_type(&name) {
+            Some(v) => v,
+            None => {
+                return Err(Error::new_spanned(
+                    item.name,
+                    format_args!("cppbind::type_info<{name}> not found"),
+                ))
+            }
+        };
+
+        // Get object size.
+        let size = match meta.size {
+            Some(v) => v,
+            None => {
+                return Err(Error::new_spanned(
+                    item.name,
+                    format_args!("cppbind::type_info<{name}>::size not found"),
+                ));
+            }
+        };
+
+        // Get alignment.
+        let align = match meta.align {
+            Some(v) => v,
+            None => {
+                return Err(Error::new_spanned(
+                    item.name,
+                    format_args!("cppbind::type_info<{name}>::align not found"),
+                ))
+            }
+        };
+
+        // Get allocation functions. Aligned allocation is only used when the class is over-aligned
+        // the same as C++ new expression.
+        let new_align = match meta.new_align {
+            Some(v) => v,
+            None => {
+                return Err(Error::new_spanned(
+                    item.name,
+                    format_args!("cppbind::type_info<{name}>::new_align not found"),
+                ));
+            }
+        };
+
+        let aligned_new = match meta.aligned_new {
+            Some(v) => v,
+            None => {
+                return Err(Error::new_spanned(
+                    item.name,
+                    format_args!("cppbind::type_info<{name}>::aligned_new not found"),
+                ));
+            }
+        };
+
+        // We cannot silently fallback to the unaligned allocation since the memory will not be
+        // aligned the same as the class.
+        if new_align == 0 {
+            return Err(Error::new_spanned(
+                item.name,
+                format_args!("couldn't determine if '{name}' is over-aligned since cppbind::type_info<{name}>::new_align is zero"),
+            ));
+        }
+
+        let over_aligned = align > new_align;
+
+        if over_aligned && !aligned_new {
+            return Err(Error::new_spanned(
+                item.name,
+                format_args!("'{name}' is over-aligned but aligned operator new is not available (C++17 or -faligned-new is required)"),
+            ));
+        }
+
+        let sized = match meta.sized_delete {
+            Some(v) => v,
+            None => {
+                return Err(Error::new_spanned(
+                    item.name,
+                    format_args!("cppbind::type_info<{name}>::sized_delete not found"),
+                ));
+            }
+        };
+
+        let polymorphic = match meta.polymorphic {
+            Some(v) => v,
+            None => {
+                return Err(Error::new_spanned(
+                    item.name,
+                    format_args!("cppbind::type_info<{name}>::polymorphic not found"),
+                ));
+            }
+        };
+
+        let virtual_dtor = match meta.virtual_destructor {
+            Some(v) => v,
+            None => {
+                return Err(Error::new_spanned(
+                    item.name,
+                    format_args!("cppbind::type_info<{name}>::virtual_destructor not found"),
+                ));
+            }
+        };
+
+        // Class with a trivial destructor does not have a symbol for it.
+        let trivial = match meta.trivially_destructible {
+            Some(v) => v,
+            None => {
+                return Err(Error::new_spanned(
+                    item.name,
+                    format_args!("cppbind::type_info<{name}>::trivially_destructible not found"),
+                ));
+            }
+        };
+
+        let (mut alloc, try_alloc, mut dealloc) = if over_aligned {
+            let align = Literal::usize_unsuffixed(align);
+
+            (
+                quote!(::cppbind::new_aligned(#size, #align)),
+                quote!(::cppbind::new_aligned_nothrow(#size, #align, &raw const ::cppbind::NOTHROW)),
+                if sized {
+                    quote!(::cppbind::delete_aligned(this, #size, #align))
+                } else {
+                    quote!(::cppbind::delete_unsized_aligned(this, #align))
+                },
+            )
+        } else {
+            (
+                quote!(::cppbind::new(#size)),
+                quote!(::cppbind::new_nothrow(#size, &raw const ::cppbind::NOTHROW)),
+                if sized {
+                    quote!(::cppbind::delete(this, #size))
+                } else {
+                    quote!(::cppbind::delete_unsized(this))
+                },
+            )
+        };
+
+        // Array of a class with a trivial destructor does not have a cookie so it does not know its
+        // size, which need unsized deallocation.
+        let array_sized = sized && !trivial;
+        let (array_alloc, array_dealloc) = if over_aligned {
+            let align = Literal::usize_unsuffixed(align);
+
+            (
+                quote!(::cppbind::new_array_aligned(len, #align)),
+                if array_sized {
+                    quote!(::cppbind::delete_array_aligned(ptr, len, #align))
+                } else {
+                    quote!(::cppbind::delete_array_unsized_aligned(ptr, #align))
+                },
+            )
+        } else {
+            (
+                quote!(::cppbind::new_array(len)),
+                if array_sized {
+                    quote!(::cppbind::delete_array(ptr, len))
+                } else {
+                    quote!(::cppbind::delete_array_unsized(ptr))
+                },
+            )
+        };
+
+        // Check if the class can be relocated with a plain memory copy. The destructor of the source
+        // is never invoked so it must be trivial unless the class is trivially copyable, which
+        // implies it.
+        if item.relocatable {
+            let movable = match (meta.trivially_copyable, meta.trivially_move_constructible) {
+                (Some(a), Some(b)) => a || (b && trivial),
+                (None, _) => {
+                    return Err(Error::new_spanned(
+                        item.name,
+                        format_args!("cppbind::type_info<{name}>::trivially_copyable not found"),
+                    ));
+                }
+                (_, None) => {
+                    return Err(Error::new_spanned(
+                        item.name,
+                        format_args!(
+                            "cppbind::type_info<{name}>::trivially_move_constructible not found"
+                        ),
+                    ));
+                }
+            };
+
+            if !movable {
+                return Err(Error::new_spanned(
+                    item.name,
+                    format_args!(
+                        "'{name}' is not trivially copyable or trivially move constructible and trivially destructible"
+                    ),
+                ));
+            }
+        }
+
+        // Render constructors.
+        let qualified = meta.name.segments();
+        let mut impls = TokenStream::new();
+        let mut inlines = TokenStream::new();
+        let mut arrays = TokenStream::new();
+        let mut refs = TokenStream::new();
+        let mut muts = TokenStream::new();
+        let mut externs = TokenStream::new();
+
+        let this = Type::Class(meta.name.clone());
+        let copy = Type::Ref {
+            c: true,
+            t: this.clone().into(),
+        };
+        let moved = Type::RvalueRef {
+            c: false,
+            t: this.clone().into(),
+        };
+        let mut clone = None;
+        let mut relocate = None;
+
+        for (i, ctor) in item.ctors.iter().enumerate() {
+            if ctor.access != Accessibility::Public || ctor.deleted {
+                continue;
+            }
+
+            // Check if copy constructor.
+            if ctor.params.len() == 1 && resolve_type(ns, &ctor.params[0].ty) == copy {
+                clone = Some(ctor.noexcept);
+                continue;
+            }
+
+            // Check if move constructor.
+            if ctor.params.len() == 1 && resolve_type(ns, &ctor.params[0].ty) == moved {
+                relocate = Some(ctor.noexcept);
+                continue;
+            }
+
+            // Render parameters.
+            let params = self.render_params(ns, &ctor.params)?;
+            let generics = &params.generics;
+            let args = &params.args;
+            let exprs = &params.exprs;
+            let ffi_args = &params.ffi_args;
+
+            // Render wrapper.
+            let name = format_ident!("new{}", i + 1, span = ctor.span);
+            let init = format_ident!("init{}", i + 1, span = ctor.span);
+            let array = format_ident!("new{}_array", i + 1, span = ctor.span);
+            let ffi = format_ident!("{}_ctor{}", class, i + 1, span = Span::call_site());
+
+            impls.extend(quote! {
+                pub unsafe fn #name<#(#generics),*>(mut this: T, #(#args),*) -> Self {
+                    #ffi(this.as_mut_ptr(), #(#exprs),*);
+
+                    Self {
+                        mem: this,
+                        phantom: ::std::marker::PhantomData,
+                    }
+                }
+            });
+
+            arrays.extend(quote! {
+                pub unsafe fn #array<#(#generics),*>(len: usize, #(#args),*) -> ::cppbind::HeapArray<#mem> {
+                    ::cppbind::HeapArray::<#mem>::new(len, |this| #ffi(this.cast(), #(#exprs),*))
+                }
+            });
+
+            inlines.extend(quote! {
+                pub unsafe fn #init<#(#generics),*>(self: ::std::pin::Pin<&mut Self>, #(#args),*) {
+                    let this = ::cppbind::Memory::as_mut_ptr(&mut self.get_unchecked_mut().mem);
+
+                    #ffi(this, #(#exprs),*);
+                }
+            });
+
+            // Render FFI. An object constructed from Rust is always a complete object so we never
+            // need the base object constructor, which require a VTT when the class has a virtual
+            // base.
+            let mut name = qualified.to_vec();
+
+            name.push(Segment::Ctor(CtorKind::Complete));
+
+            let sym = Symbol::new(Name::Nested(name), Some(params.sig(false))).to_itanium();
+            let abi = abi(ctor.noexcept);
+
+            externs.extend(quote! {
+                unsafe extern #abi {
+                    #[link_name = #sym]
+                    fn #ffi(this: *mut (), #(#ffi_args),*);
+                }
+            });
+        }
+
+        // Render methods.
+        let methods = item
+            .members
+            .iter()
+            .filter_map(|m| match m {
+                Member::Method(v) => Some(v),
+                Member::Operator(_, _) | Member::Alloc(_) | Member::Dtor(_) => None,
+            })
+            .filter(|m| m.access == Accessibility::Public && !m.deleted)
+            .collect::<Vec<_>>();
+
+        for (i, m) in methods.iter().enumerate() {
+            // Get Rust name. Overloaded methods has a suffix with its position on the class.
+            let overloads = methods.iter().filter(|v| v.name == m.name).count();
+            let (name, ffi) = if overloads == 1 {
+                (
+                    rust_ident(&m.name),
+                    format_ident!("{}_{}", class, m.name, span = Span::call_site()),
+                )
+            } else {
+                let n = methods[..i].iter().filter(|v| v.name == m.name).count() + 1;
+
+                (
+                    format_ident!("{}{}", m.name, n),
+                    format_ident!("{}_{}{}", class, m.name, n, span = Span::call_site()),
+                )
+            };
+
+            // Render parameters.
+            let params = self.render_params(ns, &m.params)?;
+            let generics = &params.generics;
+            let args = &params.args;
+            let exprs = &params.exprs;
+            let ffi_args = &params.ffi_args;
+            let ffi_ret = self.render_return(ns, &m.ret, &m.name)?;
+            let ret = ffi_ret.clone();
+
+            // Reference to a class we know is returned as a view borrowed from the object.
+            let view = self.render_view(ns, &resolve_type(ns, &m.ret));
+
+            // Render wrapper.
+            let (this, ptr, ffi_this) = if m.c {
+                (quote!(&self), quote!(as_ptr), quote!(*const ()))
+            } else {
+                (quote!(&mut self), quote!(as_mut_ptr), quote!(*mut ()))
+            };
+            let call = match &view {
+                Some(v) => quote!(#v::from_ptr(#ffi(this, #(#exprs),*))),
+                None => quote!(#ffi(this, #(#exprs),*)),
+            };
+            let ret = match &view {
+                Some(v) => quote!(-> #v<'_>),
+                None => ret,
+            };
+
+            // A throwing method is called through a thunk that catch the exception. The thunk use a
+            // pointer-to-member so a virtual method is also dispatched by C++.
+            if m.throws {
+                if overloads != 1 {
+                    return Err(Error::new_spanned(
+                        &m.name,
+                        "overloaded throwing method is not supported yet",
+                    ));
+                }
+
+                let key = m.name.to_string();
+
+                if !meta.throwing.contains(&key) {
+                    return Err(Error::new_spanned(
+                        &m.name,
+                        format_args!(
+                            "cppbind::throwing_info<{}, cppbind::method::{key}>::thunk not found",
+                            meta.name
+                        ),
+                    ));
+                }
+
+                // Render wrapper.
+                let out = self
+                    .render_output(ns, &resolve_type(ns, &m.ret), &m.name)?
+                    .unwrap_or_else(|| quote!(()));
+                let thunk = format_ident!("{}_thunk", ffi);
+                let (ok, map) = match &view {
+                    Some(v) => (quote!(#v<'_>), quote!(.map(|v| #v::from_ptr(v)))),
+                    None => (out.clone(), quote!()),
+                };
+                let wrapper = |ptr: TokenStream| {
+                    quote! {
+                        pub unsafe fn #name<#(#generics),*>(#this, #(#args),*) -> ::std::result::Result<#ok, ::cppbind::Exception> {
+                            let this = #ptr;
+
+                            ::cppbind::try_invoke(|e, r| #thunk(e, r, this, #(#exprs),*))#map
+                        }
+                    }
+                };
+
+                impls.extend(wrapper(quote!(self.mem.#ptr())));
+
+                if m.c {
+                    refs.extend(wrapper(quote!(self.ptr)));
+                } else {
+                    muts.extend(wrapper(quote!(self.ptr)));
+                }
+
+                // Render FFI.
+                let sym = Symbol::new(
+                    Name::Nested(vec![
+                        Segment::Ident("cppbind".into()),
+                        Segment::Ident("throwing_info".into()),
+                        Segment::TemplateArg(TemplateArg::Type(
+                            Type::Class(meta.name.clone()).into(),
+                        )),
+                        Segment::TemplateArg(TemplateArg::Type(
+                            Type::Class(Name::Nested(vec![
+                                Segment::Ident("cppbind".into()),
+                                Segment::Ident("method".into()),
+                                Segment::Ident(key.into()),
+                            ]))
+                            .into(),
+                        )),
+                        Segment::Ident("thunk".into()),
+                    ]),
+                    None,
+                )
+                .to_itanium();
+
+                // The thunk rethrow the forced unwind (e.g. thread cancellation) so it can unwind.
+                externs.extend(quote! {
+                    unsafe extern "C" {
+                        #[link_name = #sym]
+                        static #thunk: unsafe extern "C-unwind" fn(*mut ::cppbind::Catcher, *mut #out, #ffi_this, #(#ffi_args),*) -> bool;
+                    }
+                });
+
+                continue;
+            }
+
+            // A virtual method is called through the vtable so the override is invoked.
+            let abi = abi(m.noexcept);
+            let lookup = if m.virt {
+                if overloads != 1 {
+                    return Err(Error::new_spanned(
+                        &m.name,
+                        "overloaded virtual method is not supported yet",
+                    ));
+                }
+
+                // Get vtable offset.
+                let key = m.name.to_string();
+                let off = match meta.virtuals.get(&key) {
+                    Some(&Some(v)) => v,
+                    Some(None) => {
+                        return Err(Error::new_spanned(
+                            &m.name,
+                            format_args!("{}::{key} is not virtual", meta.name),
+                        ));
+                    }
+                    None => {
+                        return Err(Error::new_spanned(
+                            &m.name,
+                            format_args!(
+                                "cppbind::virtual_info<{}, cppbind::method::{key}>::member not found",
+                                meta.name
+                            ),
+                        ));
+                    }
+                };
+
+                quote! {
+                    let #ffi: unsafe extern #abi fn(#ffi_this, #(#ffi_args),*) #ffi_ret =
+                        ::std::mem::transmute(::cppbind::virtual_fn(this.cast(), #off));
+                }
+            } else {
+                quote!()
+            };
+
+            let wrapper = |ptr: TokenStream| {
+                quote! {
+                    pub unsafe fn #name<#(#generics),*>(#this, #(#args),*) #ret {
+                        let this = #ptr;
+                        #lookup
+                        #call
+                    }
+                }
+            };
+
+            impls.extend(wrapper(quote!(self.mem.#ptr())));
+
+            if m.c {
+                refs.extend(wrapper(quote!(self.ptr)));
+            } else {
+                muts.extend(wrapper(quote!(self.ptr)));
+            }
+
+            // Render FFI.
+            if m.virt {
+                continue;
+            }
+
+            let mut name = qualified.to_vec();
+
+            name.push(Segment::Ident(m.name.to_string().into()));
+
+            let sym = Symbol::new(Name::Nested(name), Some(params.sig(m.c))).to_itanium();
+
+            externs.extend(quote! {
+                unsafe extern #abi {
+                    #[link_name = #sym]
+                    fn #ffi(this: #ffi_this, #(#ffi_args),*) #ffi_ret;
+                }
+            });
+        }
+
+        // Render operators. Function call operator is a method named "call" since Fn traits cannot
+        // be implemented on stable Rust.
+        let mut overloads = Vec::new();
+        let mut assign = false;
+        let calls = item
+            .members
+            .iter()
+            .filter_map(|m| match m {
+                Member::Operator(Accessibility::Public, f) if !f.deleted => Some(f),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        for f in &calls {
+            // Build symbol.
+            let params = f
+                .params
+                .iter()
+                .map(|p| (resolve_type(ns, &p.ty), p.name.clone()))
+                .collect::<Vec<_>>();
+            let sig = if params.is_empty() {
+                vec![Type::Void]
+            } else {
+                params.iter().map(|p| p.0.clone()).collect()
+            };
+
+            let mut name = qualified.to_vec();
+
+            name.push(Segment::Operator(f.op));
+
+            let sym =
+                Symbol::new(Name::Nested(name), Some(Signature::new(None, sig, f.c))).to_itanium();
+            let abi = abi(f.noexcept);
+
+            // Copy assignment operator is used by clone_from.
+            if f.op == Operator::Assign && params.len() == 1 && params[0].0 == copy {
+                let ffi = format_ident!("{}_assign", class, span = Span::call_site());
+                let ret = self.render_return(ns, &f.ret, &Ident::new("operator", f.span))?;
+
+                impls.extend(quote! {
+                    pub unsafe fn assign<M: ::cppbind::Memory<Class = #class<M>>>(&mut self, other: &#class<M>) {
+                        #ffi(self.mem.as_mut_ptr(), other.mem.as_ptr());
+                    }
+                });
+
+                externs.extend(quote! {
+                    unsafe extern #abi {
+                        #[link_name = #sym]
+                        fn #ffi(this: *mut (), other: *const ()) #ret;
+                    }
+                });
+
+                assign = true;
+                continue;
+            }
+
+            // Move assignment operator consumes the source.
+            if f.op == Operator::Assign && params.len() == 1 && params[0].0 == moved {
+                let ffi = format_ident!("{}_move_assign", class, span = Span::call_site());
+                let ret = self.render_return(ns, &f.ret, &Ident::new("operator", f.span))?;
+
+                impls.extend(quote! {
+                    pub unsafe fn move_assign<M: ::cppbind::Memory<Class = #class<M>>>(&mut self, mut src: #class<M>) {
+                        #ffi(self.mem.as_mut_ptr(), src.mem.as_mut_ptr());
+                    }
+                });
+
+                externs.extend(quote! {
+                    unsafe extern #abi {
+                        #[link_name = #sym]
+                        fn #ffi(this: *mut (), other: *mut ()) #ret;
+                    }
+                });
+
+                continue;
+            }
+
+            if f.op != Operator::Call {
+                overloads.push(Overload {
+                    op: f.op,
+                    span: Ident::new("operator", f.span),
+                    lhs: Type::Ref {
+                        c: f.c,
+                        t: this.clone().into(),
+                    },
+                    rhs: params,
+                    ret: resolve_type(ns, &f.ret),
+                    noexcept: f.noexcept,
+                    sym,
+                });
+
+                continue;
+            }
+
+            // Get Rust name.
+            let total = calls.iter().filter(|v| v.op == Operator::Call).count();
+            let n = calls
+                .iter()
+                .take_while(|v| !std::ptr::eq(**v, *f))
+                .filter(|v| v.op == Operator::Call)
+                .count()
+                + 1;
+            let name = if total == 1 {
+                format_ident!("call", span = f.span)
+            } else {
+                format_ident!("call{}", n, span = f.span)
+            };
+
+            // Render wrapper.
+            let ffi = format_ident!("{}_cl{}", class, n, span = Span::call_site());
+            let span = Ident::new("operator", f.span);
+            let p = self.render_params(ns, &f.params)?;
+            let generics = &p.generics;
+            let args = &p.args;
+            let ffi_args = &p.ffi_args;
+            let (this, ptr, ffi_this) = if f.c {
+                (quote!(&self), quote!(as_ptr), quote!(*const ()))
+            } else {
+                (quote!(&mut self), quote!(as_mut_ptr), quote!(*mut ()))
+            };
+            let exprs = std::iter::once(quote!(self.mem.#ptr()))
+                .chain(p.exprs.iter().cloned())
+                .collect::<Vec<_>>();
+            let call = self.render_call(ns, &ffi, &exprs, &resolve_type(ns, &f.ret), &span)?;
+            let out = &call.out;
+            let ret = if f.ret == Type::Void {
+                TokenStream::new()
+            } else {
+                quote!(-> #out)
+            };
+            let expr = &call.expr;
+            let sret = &call.sret;
+            let ffi_ret = &call.ret;
+
+            impls.extend(quote! {
+                pub unsafe fn #name<#(#generics),*>(#this, #(#args),*) #ret {
+                    #expr
+                }
+            });
+
+            externs.extend(quote! {
+                unsafe extern #abi {
+                    #[link_name = #sym]
+                    fn #ffi(#sret this: #ffi_this, #(#ffi_args),*) #ffi_ret;
+                }
+            });
+        }
+
+        let operators = self.render_operators(ns, &class.to_string(), overloads)?;
+
+        // Render copy constructor.
+        if let Some(noexcept) = clone {
+            let ffi = format_ident!("{}_copy", class, span = Span::call_site());
+            let mut name = qualified.to_vec();
+
+            name.push(Segment::Ctor(CtorKind::Complete));
+
+            let sig = Signature::new(None, vec![copy.clone()], false);
+            let sym = Symbol::new(Name::Nested(name), Some(sig)).to_itanium();
+            let clone_from = assign.then(|| {
+                quote! {
+                    fn clone_from(&mut self, source: &Self) {
+                        unsafe { self.assign(source) };
+                    }
+                }
+            });
+
+            impls.extend(quote! {
+                pub unsafe fn clone_into<M: ::cppbind::Memory<Class = #class<M>>>(&self, mut mem: M) -> #class<M> {
+                    #ffi(mem.as_mut_ptr(), self.mem.as_ptr());
+
+                    #class {
+                        mem,
+                        phantom: ::std::marker::PhantomData,
+                    }
+                }
+            });
+
+            let abi = abi(noexcept);
+
+            externs.extend(quote! {
+                unsafe extern #abi {
+                    #[link_name = #sym]
+                    fn #ffi(this: *mut (), other: *const ());
+                }
+
+                impl Clone for #class<::cppbind::Heap<#mem>> {
+                    fn clone(&self) -> Self {
+                        unsafe { self.clone_into(::cppbind::Heap::new()) }
+                    }
+
+                    #clone_from
+                }
+            });
+        }
+
+        // Generate destructor FFI.
+        let dtor = format_ident!("{}_dtor", class, span = Span::call_site());
+
+        // Render move constructor. The moved-from object is destroyed right after the move so the
+        // source memory can be released.
+        if let Some(noexcept) = relocate {
+            let ffi = format_ident!("{}_move", class, span = Span::call_site());
+            let mut name = qualified.to_vec();
+
+            name.push(Segment::Ctor(CtorKind::Complete));
+
+            let sig = Signature::new(None, vec![moved.clone()], false);
+            let sym = Symbol::new(Name::Nested(name), Some(sig)).to_itanium();
+
+            let drop = (!trivial).then(|| quote!(#dtor(src.mem.as_mut_ptr());));
+
+            impls.extend(quote! {
+                pub unsafe fn move_into<M: ::cppbind::Memory<Class = #class<M>>>(self, mut dst: M) -> #class<M> {
+                    let mut src = ::std::mem::ManuallyDrop::new(self);
+
+                    #ffi(dst.as_mut_ptr(), src.mem.as_mut_ptr());
+                    #drop
+                    ::std::ptr::drop_in_place(&mut src.mem);
+
+                    #class {
+                        mem: dst,
+                        phantom: ::std::marker::PhantomData,
+                    }
+                }
+            });
+
+            let abi = abi(noexcept);
+
+            externs.extend(quote! {
+                unsafe extern #abi {
+                    #[link_name = #sym]
+                    fn #ffi(this: *mut (), other: *mut ());
+                }
+            });
+        }
+
+        let void = Type::Ptr {
+            c: false,
+            t: Type::Void.into(),
+        };
+
+        // Trivially destructible class does not have a symbol for its destructor. The object is
+        // destroyed by its memory since a heap object need to be destroyed with the delete
+        // expression when the destructor is virtual.
+        let mut drop = TokenStream::new();
+        let mut delete = quote!(Self::dealloc(this));
+
+        // Destructor is implicitly noexcept, including the one that was not declared.
+        let abi = abi(item
+            .members
+            .iter()
+            .find_map(|m| match m {
+                Member::Dtor(v) => Some(v.noexcept),
+                _ => None,
+            })
+            .unwrap_or(true));
+
+        if !trivial {
+            let mut name = qualified.to_vec();
+
+            name.push(Segment::Dtor(DtorKind::Complete));
+
+            let sym = Symbol::new(
+                Name::Nested(name),
+                Some(Signature::new(None, vec![Type::Void], false)),
+            )
+            .to_itanium();
+
+            drop.extend(quote! {
+                impl<T: ::cppbind::Memory<Class = Self>> Drop for #class<T> {
+                    fn drop(&mut self) {
+                        unsafe { self.mem.destroy() };
+                    }
+                }
+            });
+
+            externs.extend(quote! {
+                unsafe extern #abi {
+                    #[link_name = #sym]
+                    fn #dtor(this: *mut ());
+                }
+            });
+
+            delete = quote! {
+                #dtor(this);
+                Self::dealloc(this);
+            };
+        }
+
+        // Calling the deleting destructor directly will not invoke the destructor of the most
+        // derived class so we let C++ dispatch it through the vtable.
+        if virtual_dtor {
+            if !meta.deleter {
+                return Err(Error::new_spanned(
+                    item.name,
+                    format_args!("cppbind::type_info<{name}>::deleter not found"),
+                ));
+            }
+
+            let ffi = format_ident!("{}_deleter", class, span = Span::call_site());
+            let sym = Symbol::new(
+                Name::Nested(vec![
+                    Segment::Ident("cppbind".into()),
+                    Segment::Ident("type_info".into()),
+                    Segment::TemplateArg(TemplateArg::Type(this.clone().into())),
+                    Segment::Ident("deleter".into()),
+                ]),
+                Some(Signature::new(None, vec![void.clone()], false)),
+            )
+            .to_itanium();
+
+            externs.extend(quote! {
+                unsafe extern #abi {
+                    #[link_name = #sym]
+                    fn #ffi(this: *mut ());
+                }
+            });
+
+            delete = quote!(#ffi(this));
+        }
+
+        // Array of a class with non-trivial destructor has a cookie to store the number of
+        // elements.
+        let (cookie, destroy) = if trivial {
+            (0, TokenStream::new())
+        } else {
+            (
+                align.max(std::mem::size_of::<u64>()),
+                quote!(#dtor(this.cast());),
+            )
+        };
+
+        // Render class-specific allocation functions. These take precedence over the global one
+        // the same as C++ new expression.
+        let allocs = item.members.iter().filter_map(|m| match m {
+            Member::Alloc(v) => Some(v),
+            _ => None,
+        });
+
+        let mut nothrow = None;
+
+        for f in allocs {
+            let params = f.params.iter().map(|p| &p.ty).collect::<Vec<_>>();
+            let (seg, sig, decl) = if f.delete {
+                let sized = match params.as_slice() {
+                    [p] if **p == void => false,
+                    [p, n] if **p == void && is_size_t(n) => true,
+                    _ => {
+                        return Err(Error::new(
+                            f.span,
+                            "only operator delete(void*) and operator delete(void*, size_t) are supported",
+                        ));
+                    }
+                };
+
+                if f.ret != Type::Void {
+                    return Err(Error::new(f.span, "operator delete must return void"));
+                }
+
+                let ffi = format_ident!("{}_delete", class, span = Span::call_site());
+
+                if sized {
+                    dealloc = quote!(#ffi(this, #size));
+
+                    (
+                        Segment::Delete,
+                        vec![void.clone(), Type::Ulong],
+                        quote!(fn #ffi(ptr: *mut (), len: usize);),
+                    )
+                } else {
+                    dealloc = quote!(#ffi(this));
+
+                    (
+                        Segment::Delete,
+                        vec![void.clone()],
+                        quote!(fn #ffi(ptr: *mut ());),
+                    )
+                }
+            } else {
+                if f.ret != void {
+                    return Err(Error::new(f.span, "operator new must return void*"));
+                }
+
+                match params.as_slice() {
+                    [n] if is_size_t(n) => {
+                        let ffi = format_ident!("{}_new", class, span = Span::call_site());
+
+                        alloc = quote!(#ffi(#size));
+                        nothrow.get_or_insert(None);
+
+                        (
+                            Segment::New,
+                            vec![Type::Ulong],
+                            quote!(fn #ffi(len: usize) -> *mut ();),
+                        )
+                    }
+                    [n, t] if is_size_t(n) && is_nothrow(t) => {
+                        let ffi = format_ident!("{}_new_nothrow", class, span = Span::call_site());
+
+                        nothrow = Some(Some(quote!(#ffi(#size, &raw const ::cppbind::NOTHROW))));
+
+                        (
+                            Segment::New,
+                            vec![Type::Ulong, (*t).clone()],
+                            quote!(fn #ffi(len: usize, tag: *const u8) -> *mut ();),
+                        )
+                    }
+                    _ => {
+                        return Err(Error::new(
+                            f.span,
+                            "only operator new(size_t) and operator new(size_t, const std::nothrow_t&) are supported",
+                        ));
+                    }
+                }
+            };
+
+            let sym = Symbol::new(
+                Name::Nested(qualified.iter().cloned().chain([seg]).collect()),
+                Some(Signature::new(None, sig, false)),
+            )
+            .to_itanium();
+
+            externs.extend(quote! {
+                unsafe extern "C-unwind" {
+                    #[link_name = #sym]
+                    #decl
+                }
+            });
+        }
+
+        // Class-specific operator new hides the global nothrow version.
+        let try_alloc = match nothrow {
+            Some(Some(v)) => Some(v),
+            Some(None) => None,
+            None => Some(try_alloc),
+        }
+        .map(|v| {
+            quote! {
+                impl ::cppbind::TryHeapAlloc for #mem {
+                    fn try_alloc() -> *mut () {
+                        unsafe { #v }
+                    }
+                }
+            }
+        });
+
+        // Relocatable class does not need to be pinned so it can live inside Rust containers.
+        let (pinned, init, owned) = if item.relocatable {
+            let owned = quote! {
+                impl ::cppbind::Memory for #mem {
+                    type Class = #class<Self>;
+
+                    fn as_ptr(&self) -> *const () {
+                        self.data.as_ptr().cast()
+                    }
+
+                    fn as_mut_ptr(&mut self) -> *mut () {
+                        self.data.as_mut_ptr().cast()
+                    }
+
+                    unsafe fn destroy(&mut self) {
+                        let this = self.as_mut_ptr();
+
+                        #destroy
+                    }
+                }
+            };
+
+            (TokenStream::new(), TokenStream::new(), owned)
+        } else {
+            (
+                quote!(phantom: ::std::marker::PhantomPinned,),
+                quote!(phantom: ::std::marker::PhantomPinned,),
+                TokenStream::new(),
+            )
+        };
+
+        // Render base classes.
+        let publics = item
+            .bases
+            .iter()
+            .filter(|b| b.access == Accessibility::Public)
+            .collect::<Vec<_>>();
+
+        for (i, b) in publics.iter().enumerate() {
+            // Get base class.
+            let base = match resolve_type(ns, &b.ty) {
+                Type::Class(v) => v,
+                _ => unreachable!(),
+            };
+
+            let key = base.to_string();
+            let path = match self.classes.get(&key) {
+                Some(v) => v,
+                None => {
+                    return Err(Error::new(
+                        b.span,
+                        format_args!("base class '{key}' must be declared in cpp!"),
+                    ));
+                }
+            };
+
+            // Get base offset. Offset of a virtual base need to be lookup from the vtable at
+            // runtime, which we let the C++ compiler do it.
+            let cast = if b.virt {
+                if !meta.virtual_bases.contains(&key) {
+                    return Err(Error::new(
+                        b.span,
+                        format_args!("cppbind::base_info<{name}, {key}>::cast not found"),
+                    ));
+                }
+
+                let ffi = format_ident!("{}_to_base{}", class, i + 1, span = Span::call_site());
+                let sym = Symbol::new(
+                    Name::Nested(vec![
+                        Segment::Ident("cppbind".into()),
+                        Segment::Ident("base_info".into()),
+                        Segment::TemplateArg(TemplateArg::Type(this.clone().into())),
+                        Segment::TemplateArg(TemplateArg::Type(Type::Class(base).into())),
+                        Segment::Ident("cast".into()),
+                    ]),
+                    Some(Signature::new(None, vec![void.clone()], false)),
+                )
+                .to_itanium();
+
+                externs.extend(quote! {
+                    unsafe extern "C-unwind" {
+                        #[link_name = #sym]
+                        fn #ffi(this: *mut ()) -> *mut ();
+                    }
+                });
+
+                Err(ffi)
+            } else {
+                match meta.bases.get(&key) {
+                    Some(&v) => Ok(Literal::usize_unsuffixed(v)),
+                    None => {
+                        return Err(Error::new(
+                            b.span,
+                            format_args!("cppbind::base_info<{name}, {key}>::offset not found"),
+                        ));
+                    }
+                }
+            };
+
+            let upcast = |ptr: TokenStream| match &cast {
+                Ok(off) => quote!(#ptr.cast::<u8>().add(#off).cast()),
+                Err(ffi) => quote!(#ffi(#ptr as *mut ()).cast()),
+            };
+
+            // Get Rust types.
+            let mut path = path.clone();
+            let last = path.pop().unwrap();
+            let sibling = |name: Ident| {
+                let mut path = path.clone();
+
+                path.push(name);
+
+                self.rust_path(ns, &path)
+            };
+
+            let br = sibling(view_name(&last, false));
+            let bw = sibling(view_name(&last, true));
+
+            // Render upcasts.
+            let (as_ref, as_mut) = if publics.len() == 1 {
+                (format_ident!("as_base"), format_ident!("as_base_mut"))
+            } else {
+                let name = snake_case(&last.to_string());
+
+                (format_ident!("as_{name}"), format_ident!("as_{name}_mut"))
+            };
+
+            let this = upcast(quote!(self.mem.as_ptr()));
+            let this_mut = upcast(quote!(self.mem.as_mut_ptr()));
+            let view = upcast(quote!(self.ptr));
+
+            impls.extend(quote! {
+                pub fn #as_ref(&self) -> #br<'_> {
+                    unsafe { #br::from_ptr(#this) }
+                }
+
+                pub fn #as_mut(&mut self) -> #bw<'_> {
+                    unsafe { #bw::from_ptr(#this_mut) }
+                }
+            });
+
+            refs.extend(quote! {
+                pub fn #as_ref(&self) -> #br<'_> {
+                    unsafe { #br::from_ptr(#view) }
+                }
+            });
+
+            muts.extend(quote! {
+                pub fn #as_mut(&mut self) -> #bw<'_> {
+                    unsafe { #bw::from_ptr(#view) }
+                }
+            });
+        }
+
+        // Render RTTI.
+        let r = view_name(&class, false);
+        let m = view_name(&class, true);
+        let rtti = if polymorphic {
+            let ti = format_ident!("{}_typeinfo", class, span = Span::call_site());
+            let ts = format_ident!("{}_typename", class, span = Span::call_site());
+            let ti_sym = Symbol::new_special(Special::TypeInfo, meta.name.clone()).to_itanium();
+            let ts_sym = Symbol::new_special(Special::TypeName, meta.name.clone()).to_itanium();
+            let methods = |ptr: TokenStream| {
+                quote! {
+                    /// Returns the mangled name of the most derived class of this object.
+                    pub fn type_name(&self) -> &'static ::std::ffi::CStr {
+                        unsafe { ::cppbind::type_name(#ptr) }
+                    }
+
+                    pub fn dynamic_cast<U: ::cppbind::Polymorphic>(&self) -> Option<U::Ref<'_>> {
+                        unsafe {
+                            let src = (&raw const #ti).cast();
+                            let ptr = ::cppbind::dynamic_cast(#ptr, src, U::type_info(), -1);
+
+                            if ptr.is_null() {
+                                None
+                            } else {
+                                Some(U::to_ref(ptr.cast()))
+                            }
+                        }
+                    }
+                }
+            };
+            let methods_mut = |ptr: TokenStream| {
+                quote! {
+                    pub fn dynamic_cast_mut<U: ::cppbind::Polymorphic>(&mut self) -> Option<U::Mut<'_>> {
+                        unsafe {
+                            let src = (&raw const #ti).cast();
+                            let ptr = ::cppbind::dynamic_cast(#ptr, src, U::type_info(), -1);
+
+                            if ptr.is_null() {
+                                None
+                            } else {
+                                Some(U::to_mut(ptr.cast()))
+                            }
+                        }
+                    }
+                }
+            };
+
+            impls.extend(methods(quote!(self.mem.as_ptr())));
+            impls.extend(methods_mut(quote!(self.mem.as_mut_ptr())));
+            refs.extend(methods(quote!(self.ptr)));
+            muts.extend(methods_mut(quote!(self.ptr)));
+
+            externs.extend(quote! {
+                unsafe extern "C" {
+                    #[link_name = #ti_sym]
+                    static #ti: u8;
+                    #[link_name = #ts_sym]
+                    static #ts: ::std::ffi::c_char;
+                }
+            });
+
+            quote! {
+                impl ::cppbind::Polymorphic for #mem {
+                    type Ref<'a> = #r<'a>;
+                    type Mut<'a> = #m<'a>;
+
+                    fn type_info() -> *const () {
+                        (&raw const #ti).cast()
+                    }
+
+                    fn type_name() -> &'static ::std::ffi::CStr {
+                        unsafe { ::std::ffi::CStr::from_ptr(&raw const #ts) }
+                    }
+
+                    unsafe fn to_ref<'a>(this: *const Self) -> Self::Ref<'a> {
+                        #r::from_ptr(this)
+                    }
+
+                    unsafe fn to_mut<'a>(this: *mut Self) -> Self::Mut<'a> {
+                        #m::from_ptr(this)
+                    }
+                }
+            }
+        } else {
+            TokenStream::new()
+        };
+
+        // Render adapter.
+        let adapter = self.render_adapter(ns, &item, meta, &mem)?;
+
+        // Compose.
+        let align = Literal::usize_unsuffixed(align);
+
+        Ok(quote! {
+            #[allow(non_camel_case_types)]
+            pub struct #class<T: ::cppbind::Memory<Class = Self>> {
+                mem: T,
+                phantom: ::std::marker::PhantomData<::std::rc::Rc<()>>,
+            }
+
+            impl<T: ::cppbind::Memory<Class = Self>> #class<T> {
+                #impls
+
+                pub unsafe fn assume_init(mem: T) -> Self {
+                    Self {
+                        mem,
+                        phantom: ::std::marker::PhantomData,
+                    }
+                }
+
+                pub fn as_ptr(&self) -> *const #mem {
+                    self.mem.as_ptr().cast()
+                }
+
+                pub fn as_mut_ptr(&mut self) -> *mut #mem {
+                    self.mem.as_mut_ptr().cast()
+                }
+
+                pub fn as_ref(&self) -> #r<'_> {
+                    unsafe { #r::from_ptr(self.as_ptr()) }
+                }
+
+                pub fn as_mut(&mut self) -> #m<'_> {
+                    unsafe { #m::from_ptr(self.as_mut_ptr()) }
+                }
+            }
+
+            /// Borrowed object that is owned by someone else.
+            #[allow(non_camel_case_types)]
+            #[derive(Clone, Copy)]
+            pub struct #r<'a> {
+                ptr: *const (),
+                phantom: ::std::marker::PhantomData<(&'a #mem, ::std::rc::Rc<()>)>,
+            }
+
+            impl<'a> #r<'a> {
+                pub unsafe fn from_ptr(ptr: *const #mem) -> Self {
+                    Self {
+                        ptr: ptr.cast(),
+                        phantom: ::std::marker::PhantomData,
+                    }
+                }
+
+                pub fn as_ptr(&self) -> *const #mem {
+                    self.ptr.cast()
+                }
+
+                #refs
+            }
+
+            /// Mutably borrowed object that is owned by someone else.
+            #[allow(non_camel_case_types)]
+            pub struct #m<'a> {
+                ptr: *mut (),
+                phantom: ::std::marker::PhantomData<(&'a mut #mem, ::std::rc::Rc<()>)>,
+            }
+
+            impl<'a> #m<'a> {
+                pub unsafe fn from_ptr(ptr: *mut #mem) -> Self {
+                    Self {
+                        ptr: ptr.cast(),
+                        phantom: ::std::marker::PhantomData,
+                    }
+                }
+
+                pub fn as_ptr(&self) -> *const #mem {
+                    self.ptr.cast()
+                }
+
+                pub fn as_mut_ptr(&mut self) -> *mut #mem {
+                    self.ptr.cast()
+                }
+
+                pub fn as_ref(&self) -> #r<'_> {
+                    unsafe { #r::from_ptr(self.as_ptr()) }
+                }
+
+                #refs
+                #muts
+            }
+
+            impl #class<::cppbind::Heap<#mem>> {
+                /// Adopts an object that was created by C++ `new`.
+                ///
+                /// # Safety
+                /// `ptr` must point to a constructed object that was allocated with the same
+                /// `operator new` as `HeapAlloc::alloc()` of its memory type. The object will be
+                /// destroyed and released with the matched `operator delete` when dropped.
+                pub unsafe fn from_raw(ptr: *mut #mem) -> Self {
+                    Self::assume_init(::cppbind::Heap::from_raw(ptr))
+                }
+
+                #arrays
+
+                /// Releases the object to C++ without destroying it. The caller is responsible for
+                /// destroying it with C++ `delete`.
+                pub fn into_raw(self) -> *mut #mem {
+                    let this = ::std::mem::ManuallyDrop::new(self);
+                    let mem = unsafe { ::std::ptr::read(&this.mem) };
+
+                    mem.into_raw()
+                }
+            }
+
+            impl #class<::cppbind::Inline<#mem>> {
+                /// Returns an object that is not constructed yet. One of `init` methods must be
+                /// called after it was pinned before using or dropping it.
+                pub const unsafe fn uninit() -> Self {
+                    Self {
+                        mem: ::cppbind::Inline::new(#mem::new()),
+                        phantom: ::std::marker::PhantomData,
+                    }
+                }
+
+                #inlines
+            }
+
+            #drop
+
+            #[allow(non_camel_case_types)]
+            #[repr(C, align(#align))]
+            pub struct #mem {
+                data: [::std::mem::MaybeUninit<u8>; #size],
+                #pinned
+            }
+
+            impl #mem {
+                pub const fn new() -> Self {
+                    Self {
+                        data: [const { ::std::mem::MaybeUninit::uninit() }; #size],
+                        #init
+                    }
+                }
+            }
+
+            impl Default for #mem {
+                fn default() -> Self {
+                    Self::new()
+                }
+            }
+
+            impl ::cppbind::HeapAlloc for #mem {
+                type Class = #class<::cppbind::Heap<Self>>;
+
+                fn alloc() -> *mut () {
+                    unsafe { #alloc }
+                }
+
+                unsafe fn dealloc(this: *mut ()) {
+                    #dealloc;
+                }
+
+                unsafe fn delete(this: *mut ()) {
+                    #delete
+                }
+            }
+
+            #try_alloc
+
+            impl ::cppbind::BorrowedMemory for #mem {
+                type Class = #class<::cppbind::Borrowed<Self>>;
+            }
+
+            impl ::cppbind::ArrayAlloc for #mem {
+                type Ref<'a> = #r<'a>;
+                type Mut<'a> = #m<'a>;
+
+                const COOKIE: usize = #cookie;
+
+                fn alloc(len: usize) -> *mut () {
+                    unsafe { #array_alloc }
+                }
+
+                unsafe fn dealloc(ptr: *mut (), len: usize) {
+                    #array_dealloc;
+                }
+
+                unsafe fn destroy(this: *mut Self) {
+                    #destroy
+                }
+
+                unsafe fn to_ref<'a>(this: *const Self) -> Self::Ref<'a> {
+                    #r::from_ptr(this)
+                }
+
+                unsafe fn to_mut<'a>(this: *mut Self) -> Self::Mut<'a> {
+                    #m::from_ptr(this)
+                }
+            }
+
+            impl ::cppbind::InlineMemory for #mem {
+                type Class = #class<::cppbind::Inline<Self>>;
+
+                unsafe fn destroy(this: *mut Self) {
+                    #destroy
+                }
+            }
+
+            impl ::cppbind::Memory for ::std::pin::Pin<&mut #mem> {
+                type Class = #class<Self>;
+
+                fn as_ptr(&self) -> *const () {
+                    self.data.as_ptr().cast()
+                }
+
+                fn as_mut_ptr(&mut self) -> *mut () {
+                    unsafe { self.as_mut().get_unchecked_mut().data.as_mut_ptr().cast() }
+                }
+
+                unsafe fn destroy(&mut self) {
+                    let this = self.as_mut_ptr();
+
+                    #destroy
+                }
+            }
+
+            #owned
+            #operators
+            #rtti
+            #adapter
+            #externs
+        })
+    }
+
+    /// Renders a trait and an adapter to implement an interface (an abstract class without base
+    /// classes and data members) from Rust.
+    fn render_adapter(
+        &self,
+        ns: &[Ident],
+        item: &Class,
+        meta: &TypeInfo,
+        mem: &Ident,
+    ) -> syn::Result<TokenStream> {
+        // Check if the class can be implemented.
+        let class = rust_name(item);
+        let pure = item
+            .members
+            .iter()
+            .any(|m| matches!(m, Member::Method(v) if v.pure));
+
+        let name = &meta.name;
+
+        if !pure {
+            return Ok(TokenStream::new());
+        } else if !item.bases.is_empty() {
+            return Err(Error::new_spanned(
+                &item.name,
+                format_args!("couldn't generate an adapter for '{name}' since an abstract class with base classes is not supported"),
+            ));
+        } else if meta.size != Some(8) {
+            return Err(Error::new_spanned(
+                &item.name,
+                format_args!("couldn't generate an adapter for '{name}' since an abstract class with data members is not supported"),
+            ));
+        }
+
+        // The vtable layout depends on the destructor so it must be declared the same as C++.
+        let dtor = item.members.iter().find_map(|m| match m {
+            Member::Dtor(v) => Some(v.virt),
+            _ => None,
+        });
+
+        match (meta.virtual_destructor, dtor == Some(true)) {
+            (Some(true), false) => {
+                return Err(Error::new_spanned(
+                    &item.name,
+                    format_args!("'{name}' has a virtual destructor so it must be declared as virtual in cpp!"),
+                ));
+            }
+            (Some(false), true) => {
+                return Err(Error::new_spanned(
+                    &item.name,
+                    format_args!("the destructor of '{name}' is not virtual"),
+                ));
+            }
+            (Some(_), _) => {}
+            (None, _) => {
+                return Err(Error::new_spanned(
+                    &item.name,
+                    format_args!("cppbind::type_info<{name}>::virtual_destructor not found"),
+                ));
+            }
+        }
+
+        // Render virtual functions in the same order as vtable.
+        let tr = trait_name(&class);
+        let adapter = adapter_name(&class);
+        let br = view_name(&class, false);
+        let bw = view_name(&class, true);
+        let mut methods = TokenStream::new();
+        let mut trampolines = TokenStream::new();
+        let mut slots = Vec::new();
+        let mut externs = TokenStream::new();
+
+        for m in &item.members {
+            let m = match m {
+                Member::Method(v) if v.virt => v,
+                Member::Dtor(v) if v.virt => {
+                    // Complete object destructor then deleting destructor.
+                    slots.push(quote!(Self::complete_dtor as *const ()));
+                    slots.push(quote!(Self::deleting_dtor as *const ()));
+                    continue;
+                }
+                _ => continue,
+            };
+
+            // Check slot offset.
+            let key = m.name.to_string();
+            let off = slots.len() * 8;
+
+            match meta.virtuals.get(&key) {
+                Some(&Some(v)) if v == off => {}
+                Some(_) => {
+                    return Err(Error::new_spanned(
+                        &m.name,
+                        format_args!("vtable layout of {name} does not match its declaration"),
+                    ));
+                }
+                None => {
+                    return Err(Error::new_spanned(
+                        &m.name,
+                        format_args!(
+                            "cppbind::virtual_info<{name}, cppbind::method::{key}>::member not found"
+                        ),
+                    ));
+                }
+            }
+
+            // Render parameters.
+            let params = self.render_params(ns, &m.params)?;
+            let ffi_args = &params.ffi_args;
+            let ret = self.render_return(ns, &m.ret, &m.name)?;
+            let ffi_this = if m.c {
+                quote!(*const ())
+            } else {
+                quote!(*mut ())
+            };
+
+            // Use the implementation from C++ if the method is not pure.
+            if !m.pure {
+                let ffi = format_ident!("{}_{}_impl", class, m.name, span = Span::call_site());
+                let mut name = meta.name.segments().to_vec();
+
+                name.push(Segment::Ident(key.into()));
+
+                let sym = Symbol::new(Name::Nested(name), Some(params.sig(m.c))).to_itanium();
+                let abi = abi(m.noexcept);
+
+                externs.extend(quote! {
+                    unsafe extern #abi {
+                        #[link_name = #sym]
+                        fn #ffi(this: #ffi_this, #(#ffi_args),*) #ret;
+                    }
+                });
+
+                slots.push(quote!(#ffi as *const ()));
+                continue;
+            }
+
+            // Reference to a class we know is passed as a view.
+            let mut args = Vec::new();
+            let mut exprs = Vec::new();
+
+            for (i, p) in m.params.iter().enumerate() {
+                let name = match &p.name {
+                    Some(v) => rust_ident(v),
+                    None => format_ident!("arg{}", i + 1),
+                };
+                match self.render_view(ns, &params.types[i]) {
+                    Some(v) => {
+                        args.push(quote!(#name: #v<'_>));
+                        exprs.push(quote!(#v::from_ptr(#name.cast())));
+                    }
+                    None => {
+                        args.push(ffi_args[i].clone());
+                        exprs.push(quote!(#name));
+                    }
+                }
+            }
+
+            // Render trait method and its trampoline.
+            let method = rust_ident(&m.name);
+            let tramp = format_ident!("{}_tramp", m.name, span = Span::call_site());
+            let (this, inner) = if m.c {
+                (quote!(&self), quote!(&*this.cast::<Self>()))
+            } else {
+                (quote!(&mut self), quote!(&mut *this.cast::<Self>()))
+            };
+
+            methods.extend(quote! {
+                fn #method(#this, #(#args),*) #ret;
+            });
+
+            trampolines.extend(quote! {
+                unsafe extern "C" fn #tramp(this: #ffi_this, #(#ffi_args),*) #ret {
+                    let this = #inner;
+
+                    ::cppbind::abort_on_panic(|| this.inner.#method(#(#exprs),*))
+                }
+            });
+
+            slots.push(quote!(Self::#tramp as *const ()));
+        }
+
+        // The type info was declared by render_class.
+        let ti = format_ident!("{}_typeinfo", class, span = Span::call_site());
+        let len = slots.len() + 2;
+
+        let tr_doc = format!("Methods to implement `{name}` from Rust.");
+        let adapter_doc = format!(
+            "Implementation of `{name}` with [`{tr}`]. C++ can `delete` this object only when it was allocated with [`Box`]."
+        );
+
+        Ok(quote! {
+            #[doc = #tr_doc]
+            #[allow(non_camel_case_types)]
+            pub trait #tr {
+                #methods
+            }
+
+            #[doc = #adapter_doc]
+            #[allow(non_camel_case_types)]
+            #[repr(C)]
+            pub struct #adapter<I: #tr> {
+                vtable: *const *const (),
+                inner: ::std::mem::ManuallyDrop<I>,
+            }
+
+            impl<I: #tr> #adapter<I> {
+                // A generic static is not allowed so use a reference in a constant, which is
+                // guaranteed to be 'static.
+                const VTABLE: &'static [*const (); #len] = &[
+                    ::std::ptr::null(),
+                    (&raw const #ti).cast(),
+                    #(#slots),*
+                ];
+
+                pub fn new(inner: I) -> Self {
+                    Self {
+                        vtable: unsafe { Self::VTABLE.as_ptr().add(2) },
+                        inner: ::std::mem::ManuallyDrop::new(inner),
+                    }
+                }
+
+                pub fn get_ref(&self) -> &I {
+                    &self.inner
+                }
+
+                pub fn get_mut(&mut self) -> &mut I {
+                    &mut self.inner
+                }
+
+                pub fn into_inner(self) -> I {
+                    let mut this = ::std::mem::ManuallyDrop::new(self);
+
+                    unsafe { ::std::mem::ManuallyDrop::take(&mut this.inner) }
+                }
+
+                pub fn as_ptr(&self) -> *const #mem {
+                    (self as *const Self).cast()
+                }
+
+                pub fn as_mut_ptr(&mut self) -> *mut #mem {
+                    (self as *mut Self).cast()
+                }
+
+                pub fn as_base(&self) -> #br<'_> {
+                    unsafe { #br::from_ptr(self.as_ptr()) }
+                }
+
+                pub fn as_base_mut(&mut self) -> #bw<'_> {
+                    unsafe { #bw::from_ptr(self.as_mut_ptr()) }
+                }
+
+                unsafe extern "C" fn complete_dtor(this: *mut ()) {
+                    let this = &mut *this.cast::<Self>();
+
+                    ::cppbind::abort_on_panic(|| ::std::mem::ManuallyDrop::drop(&mut this.inner));
+                }
+
+                unsafe extern "C" fn deleting_dtor(this: *mut ()) {
+                    let this = ::std::boxed::Box::from_raw(this.cast::<Self>());
+
+                    ::cppbind::abort_on_panic(|| drop(this));
+                }
+
+                #trampolines
+            }
+
+            impl<I: #tr> Drop for #adapter<I> {
+                fn drop(&mut self) {
+                    unsafe { ::std::mem::ManuallyDrop::drop(&mut self.inner) };
+                }
+            }
+
+            #externs
+        })
+    }
+
+    fn render_alias(&self, ns: &[Ident], item: Alias) -> syn::Result<TokenStream> {
+        // Get metadata.
+        let ty = resolve_type(ns, &item.ty);
+        let name = ty.to_string();
+        let sig = resolve_type(ns, item.signature());
+        let meta = match META.get_type(&name) {
+            Some(v) => v,
+            None => {
+                return Err(Error::new_spanned(
+                    item.name,
+                    format_args!("cppbind::type_info<{name}> not found"),
+                ))
+            }
+        };
+
+        let size = match meta.size {
+            Some(v) => v,
+            None => {
+                return Err(Error::new_spanned(
+                    item.name,
+                    format_args!("cppbind::type_info<{name}>::size not found"),
+                ));
+            }
+        };
+
+        let align = match meta.align {
+            Some(v) => v,
+            None => {
+                return Err(Error::new_spanned(
+                    item.name,
+                    format_args!("cppbind::type_info<{name}>::align not found"),
+                ))
+            }
+        };
+
+        if !meta.construct || !meta.destroy {
+            return Err(Error::new_spanned(
+                item.name,
+                format_args!(
+                    "cppbind::function_info<{sig}>::{} not found",
+                    if meta.construct {
+                        "destroy"
+                    } else {
+                        "construct"
+                    }
+                ),
+            ));
+        }
+
+        // Render parameters of the closure. Reference to a class we know is passed as a view.
+        let (ret, params) = match &sig {
+            Type::Func { ret, params } => (ret, params),
+            _ => unreachable!(),
+        };
+
+        let mut names = Vec::with_capacity(params.len());
+        let mut ffi_args = Vec::with_capacity(params.len());
+        let mut args = Vec::with_capacity(params.len());
+        let mut exprs = Vec::with_capacity(params.len());
+
+        for (i, p) in params.iter().enumerate() {
+            let name = format_ident!("arg{}", i + 1);
+            let ffi = self.render_type(ns, p, Some(&item.name))?;
+            match self.render_view(ns, p) {
+                Some(v) => {
+                    args.push(quote!(#v<'_>));
+                    exprs.push(quote!(#v::from_ptr(#name)));
+                }
+                None => {
+                    args.push(ffi.clone());
+                    exprs.push(quote!(#name));
+                }
+            }
+
+            names.push(name);
+            ffi_args.push(ffi);
+        }
+
+        let out = self.render_return(ns, ret, &item.name)?;
+
+        // Render FFI.
+        let class = &item.name;
+        let mem = memory_name(class);
+        let construct = format_ident!("{}_construct", class, span = Span::call_site());
+        let destroy = format_ident!("{}_destroy", class, span = Span::call_site());
+        let void = Type::Ptr {
+            c: false,
+            t: Type::Void.into(),
+        };
+        let info = |n: &str, params: Vec<Type>| {
+            Symbol::new(
+                Name::Nested(vec![
+                    Segment::Ident("cppbind".into()),
+                    Segment::Ident("function_info".into()),
+                    Segment::TemplateArg(TemplateArg::Type(sig.clone().into())),
+                    Segment::Ident(n.to_owned().into()),
+                ]),
+                Some(Signature::new(None, params, false)),
+            )
+            .to_itanium()
+        };
+        let call = Type::Ptr {
+            c: false,
+            t: Type::Func {
+                ret: ret.clone(),
+                params: params.iter().cloned().chain([void.clone()]).collect(),
+            }
+            .into(),
+        };
+        let drop = Type::Ptr {
+            c: false,
+            t: Type::Func {
+                ret: Type::Void.into(),
+                params: vec![void.clone()],
+            }
+            .into(),
+        };
+        let csym = info("construct", vec![void.clone(), call, void.clone(), drop]);
+        let dsym = info("destroy", vec![void]);
+        let size = Literal::usize_unsuffixed(size);
+        let align = Literal::usize_unsuffixed(align);
+        let doc = format!("`{name}` that was constructed from Rust.");
+
+        Ok(quote! {
+            #[repr(C, align(#align))]
+            pub struct #mem([::std::mem::MaybeUninit<u8>; #size]);
+
+            #[doc = #doc]
+            pub struct #class(::std::boxed::Box<#mem>);
+
+            impl #class {
+                /// Constructs `std::function` that invoke `f`. A panic inside `f` will abort the
+                /// process.
+                pub fn new<F>(f: F) -> Self
+                where
+                    F: Fn(#(#args),*) #out + Send + Sync + 'static,
+                {
+                    unsafe extern "C" fn call<F>(#(#names: #ffi_args,)* context: *mut ::std::ffi::c_void) #out
+                    where
+                        F: Fn(#(#args),*) #out,
+                    {
+                        let f = &*context.cast::<F>();
+
+                        ::cppbind::abort_on_panic(|| f(#(#exprs),*))
+                    }
+
+                    unsafe extern "C" fn release<F>(context: *mut ::std::ffi::c_void) {
+                        ::cppbind::abort_on_panic(|| drop(::std::boxed::Box::from_raw(context.cast::<F>())));
+                    }
+
+                    let mut mem = ::std::boxed::Box::new(#mem([::std::mem::MaybeUninit::uninit(); #size]));
+                    let context = ::std::boxed::Box::into_raw(::std::boxed::Box::new(f));
+
+                    unsafe { #construct(&mut *mem as *mut #mem as *mut (), call::<F>, context.cast(), release::<F>) };
+
+                    Self(mem)
+                }
+
+                pub fn as_ptr(&self) -> *const #mem {
+                    &*self.0
+                }
+
+                pub fn as_mut_ptr(&mut self) -> *mut #mem {
+                    &mut *self.0
+                }
+            }
+
+            impl Drop for #class {
+                fn drop(&mut self) {
+                    unsafe { #destroy(self.as_mut_ptr().cast()) };
+                }
+            }
+
+            unsafe extern "C-unwind" {
+                #[link_name = #csym]
+                fn #construct(
+                    this: *mut (),
+                    f: unsafe extern "C" fn(#(#ffi_args,)* *mut ::std::ffi::c_void) #out,
+                    context: *mut ::std::ffi::c_void,
+                    drop: unsafe extern "C" fn(*mut ::std::ffi::c_void),
+                );
+            }
+
+            unsafe extern "C" {
+                #[link_name = #dsym]
+                fn #destroy(this: *mut ());
+            }
+        })
+    }
+
+    fn render_functions(&self, ns: &[Ident], items: Vec<Function>) -> syn::Result<TokenStream> {
+        // Group explicit instantiations with its template.
+        let (items, instances): (Vec<_>, Vec<_>) =
+            items.into_iter().partition(|f| f.args.is_none());
+        let mut templates = Vec::<(Function, Vec<Function>)>::new();
+
+        for item in items {
+            if templates.iter().any(|(t, _)| t.name == item.name) {
+                return Err(Error::new_spanned(
+                    item.name,
+                    "overloaded function template is not supported yet",
+                ));
+            }
+
+            templates.push((item, Vec::new()));
+        }
+
+        for item in instances {
+            match templates.iter_mut().find(|(t, _)| t.name == item.name) {
+                Some((_, v)) => v.push(item),
+                None => {
+                    return Err(Error::new_spanned(
+                        &item.name,
+                        format_args!("function template '{}' was not declared", item.name),
+                    ))
+                }
+            }
+        }
+
+        // Render templates.
+        let mut output = TokenStream::new();
+
+        for (t, instances) in templates {
+            output.extend(self.render_function(ns, t, instances)?);
+        }
+
+        Ok(output)
+    }
+
+    fn render_function(
+        &self,
+        ns: &[Ident],
+        template: Function,
+        instances: Vec<Function>,
+    ) -> syn::Result<TokenStream> {
+        // Resolve signature of the template.
+        let ret = resolve_type(ns, &bind_params(&template.ret, &template.template));
+        let params = template
+            .params
+            .iter()
+            .map(|p| resolve_type(ns, &bind_params(&p.ty, &template.template)))
+            .collect::<Vec<_>>();
+
+        // Render wrapper. Each instantiation is an implementation of a trait on its template
+        // arguments so the caller can write max::<i32>(a, b).
+        let name = rust_ident(&template.name);
+        let tr = format_ident!("{}_args", template.name);
+        let names = template
+            .params
+            .iter()
+            .enumerate()
+            .map(|(i, p)| match &p.name {
+                Some(v) => rust_ident(v),
+                None => format_ident!("arg{}", i + 1),
+            })
+            .collect::<Vec<_>>();
+        let types = (1..=names.len())
+            .map(|i| format_ident!("P{}", i))
+            .collect::<Vec<_>>();
+        let mut output = quote! {
+            pub unsafe fn #name<T: #tr>(#(#names: T::#types),*) -> T::Output {
+                T::call(#(#names),*)
+            }
+
+            #[allow(non_camel_case_types)]
+            pub trait #tr {
+                type Output;
+                #(type #types;)*
+
+                unsafe fn call(#(#names: Self::#types),*) -> Self::Output;
+            }
+        };
+
+        // Render instantiations.
+        for (i, item) in instances.into_iter().enumerate() {
+            // Check template arguments.
+            let args = item
+                .args
+                .iter()
+                .flatten()
+                .map(|a| match resolve_segment(ns, a) {
+                    Segment::TemplateArg(v) => v,
+                    _ => unreachable!(),
+                })
+                .collect::<Vec<_>>();
+
+            if args.len() != template.template.len() {
+                return Err(Error::new_spanned(
+                    item.name,
+                    format_args!(
+                        "'{}' requires {} template arguments",
+                        template.name,
+                        template.template.len()
+                    ),
+                ));
+            }
+
+            // Check if the declaration match with the template.
+            let expected = std::iter::once(&ret)
+                .chain(&params)
+                .map(|t| instantiate(t, &args))
+                .collect::<Option<Vec<_>>>();
+            let actual = std::iter::once(&item.ret)
+                .chain(item.params.iter().map(|p| &p.ty))
+                .map(|t| resolve_type(ns, t))
+                .collect::<Vec<_>>();
+
+            if expected.is_none_or(|v| v != actual) {
+                return Err(Error::new_spanned(
+                    item.name,
+                    format_args!(
+                        "the declaration does not match function template '{}'",
+                        template.name
+                    ),
+                ));
+            }
+
+            // Render the type to implement the trait.
+            let mut targs = Vec::with_capacity(args.len());
+
+            for a in &args {
+                let t = match a {
+                    TemplateArg::Type(v) => v,
+                    TemplateArg::Literal(_, _) => {
+                        return Err(Error::new_spanned(
+                            item.name,
+                            "non-type template argument is not supported yet",
+                        ))
+                    }
+                };
+
+                // Class we know is represented by its memory type.
+                let t = match t.as_ref() {
+                    Type::Class(v) if self.classes.contains_key(&v.to_string()) => {
+                        self.render_pointee(ns, t, Some(&item.name))?
+                    }
+                    t => self.render_type(ns, t, Some(&item.name))?,
+                };
+
+                targs.push(t);
+            }
+
+            let target = match targs.as_slice() {
+                [t] => t.clone(),
+                t => quote!((#(#t),*)),
+            };
+
+            // Render parameters. Reference is passed as a pointer since an associated type cannot
+            // have a lifetime.
+            let mut tys = Vec::with_capacity(actual.len() - 1);
+
+            for (t, p) in actual[1..].iter().zip(&item.params) {
+                let t = match t {
+                    Type::Ref { c, t } => Type::Ptr {
+                        c: *c,
+                        t: t.clone(),
+                    },
+                    t => t.clone(),
+                };
+
+                tys.push(self.render_type(ns, &t, p.name.as_ref().or(Some(&item.name)))?);
+            }
+
+            // Render implementation.
+            let ffi = format_ident!("{}_{}", template.name, i + 1, span = Span::call_site());
+            let out = self.render_output(ns, &actual[0], &item.name)?;
+            let ffi_ret = out.as_ref().map(|t| quote!(-> #t));
+            let out = out.unwrap_or_else(|| quote!(()));
+
+            output.extend(quote! {
+                impl #tr for #target {
+                    type Output = #out;
+                    #(type #types = #tys;)*
+
+                    unsafe fn call(#(#names: Self::#types),*) -> Self::Output {
+                        #ffi(#(#names),*)
+                    }
+                }
+            });
+
+            // Render FFI.
+            let mut name = ns
+                .iter()
+                .map(|v| Segment::Ident(v.to_string().into()))
+                .collect::<Vec<_>>();
+
+            name.push(Segment::Ident(template.name.to_string().into()));
+            name.extend(args.into_iter().map(Segment::TemplateArg));
+
+            let sig = if params.is_empty() {
+                vec![Type::Void]
+            } else {
+                params.clone()
+            };
+
+            let sym = Symbol::new(
+                Name::Nested(name),
+                Some(Signature::new(Some(ret.clone()), sig, false)),
+            )
+            .to_itanium();
+            let abi = abi(item.noexcept);
+
+            output.extend(quote! {
+                unsafe extern #abi {
+                    #[link_name = #sym]
+                    fn #ffi(#(#names: #tys),*) #ffi_ret;
+                }
+            });
+        }
+
+        Ok(output)
+    }
+
+    fn render_free_operators(
+        &self,
+        ns: &[Ident],
+        items: Vec<OperatorFunction>,
+    ) -> syn::Result<TokenStream> {
+        let mut overloads = Vec::with_capacity(items.len());
+
+        for f in items {
+            // Build symbol.
+            let mut params = f
+                .params
+                .iter()
+                .map(|p| (resolve_type(ns, &p.ty), p.name.clone()))
+                .collect::<Vec<_>>();
+            let sig = params.iter().map(|p| p.0.clone()).collect();
+            let mut name = ns
+                .iter()
+                .map(|v| Segment::Ident(v.to_string().into()))
+                .collect::<Vec<_>>();
+
+            name.push(Segment::Operator(f.op));
+
+            let sym = Symbol::new(Name::Nested(name), Some(Signature::new(None, sig, false)))
+                .to_itanium();
+
+            overloads.push(Overload {
+                op: f.op,
+                span: Ident::new("operator", f.span),
+                lhs: params.remove(0).0,
+                rhs: params,
+                ret: resolve_type(ns, &f.ret),
+                noexcept: f.noexcept,
+                sym,
+            });
+        }
+
+        self.render_operators(ns, "operator", overloads)
+    }
+
+    fn render_operators(
+        &self,
+        ns: &[Ident],
+        prefix: &str,
+        items: Vec<Overload>,
+    ) -> syn::Result<TokenStream> {
+        let mut output = TokenStream::new();
+        let mut cmps = Vec::<Vec<RenderedOperator>>::new();
+        let mut indexes = Vec::<Vec<RenderedOperator>>::new();
+        let mut nots = Vec::<(String, bool, Operator)>::new();
+
+        for (i, o) in items.into_iter().enumerate() {
+            // Get left operand.
+            let (c, lhs) = match &o.lhs {
+                Type::Ref { c, t } => match t.as_ref() {
+                    Type::Class(n) => (*c, self.classes.get(&n.to_string()).map(|v| (n, v))),
+                    _ => (*c, None),
+                },
+                _ => (false, None),
+            };
+
+            let (name, path) = match lhs {
+                Some(v) => v,
+                None => {
+                    return Err(Error::new_spanned(
+                        o.span,
+                        format_args!(
+                            "the first operand of operator{} must be a reference to a class declared in cpp!",
+                            o.op
+                        ),
+                    ))
+                }
+            };
+
+            // Render right operand.
+            let class = self.rust_path(ns, path);
+            let (operand, borrowed) = match o.op {
+                Operator::Eq
+                | Operator::Ne
+                | Operator::Lt
+                | Operator::Gt
+                | Operator::Le
+                | Operator::Ge => (format_ident!("other"), true),
+                Operator::Index => (format_ident!("index"), false),
+                _ => (format_ident!("rhs"), false),
+            };
+            let rhs = match o.rhs.first() {
+                Some((t, _)) => Some(self.render_operand(ns, t, &operand, borrowed, &o.span)?),
+                None => None,
+            };
+
+            // Render FFI.
+            let ffi = format_ident!(
+                "{}_{}{}",
+                prefix,
+                o.op.code(),
+                i + 1,
+                span = Span::call_site()
+            );
+            let sym = &o.sym;
+            let (this, ffi_this) = if c {
+                (quote!(self.as_ptr().cast()), quote!(*const ()))
+            } else {
+                (quote!(self.as_mut_ptr().cast()), quote!(*mut ()))
+            };
+            let args = std::iter::once(this)
+                .chain(rhs.iter().map(|v| v.expr.clone()))
+                .collect::<Vec<_>>();
+            let call = self.render_call(ns, &ffi, &args, &o.ret, &o.span)?;
+            let ffi_args = rhs.iter().map(|v| &v.ffi);
+            let sret = &call.sret;
+            let ffi_ret = &call.ret;
+            let abi = abi(o.noexcept);
+
+            output.extend(quote! {
+                unsafe extern #abi {
+                    #[link_name = #sym]
+                    fn #ffi(#sret this: #ffi_this, #(#ffi_args),*) #ffi_ret;
+                }
+            });
+
+            // Check if the right operand is the same class as the left operand.
+            let symmetric = o.rhs.first().is_some_and(|(t, _)| match t {
+                Type::Ref { c: true, t } => **t == Type::Class(name.clone()),
+                _ => false,
+            });
+
+            let r = RenderedOperator {
+                op: o.op,
+                span: o.span,
+                c,
+                class,
+                ffi,
+                rhs,
+                symmetric,
+                call,
+            };
+
+            // Group the operators that implement the same trait.
+            let group = match r.op {
+                Operator::Eq
+                | Operator::Ne
+                | Operator::Lt
+                | Operator::Gt
+                | Operator::Le
+                | Operator::Ge => &mut cmps,
+                Operator::Index => &mut indexes,
+                _ => {
+                    // Both operator! and operator~ are mapped to Not so only one of them can be
+                    // implemented on the same operand.
+                    if matches!(r.op, Operator::Not | Operator::BitNot) {
+                        let key = (r.class.to_string(), r.c);
+
+                        if let Some((_, _, op)) =
+                            nots.iter().find(|v| (&v.0, v.1) == (&key.0, key.1))
+                        {
+                            return Err(Error::new_spanned(
+                                r.span,
+                                format_args!(
+                                    "operator{} cannot be declared together with operator{op} since both are mapped to Not",
+                                    r.op
+                                ),
+                            ));
+                        }
+
+                        nots.push((key.0, key.1, r.op));
+                    }
 
-    for item in items.0 {
-        match item {
-            Declaration::Class(i) => output.extend(render_class(i)?),
+                    output.extend(self.render_operator(r)?);
+                    continue;
+                }
+            };
+
+            if !r.c && r.op != Operator::Index {
+                return Err(Error::new_spanned(
+                    r.span,
+                    format_args!("operator{} must be const", r.op),
+                ));
+            }
+
+            match group.iter_mut().find(|g| g[0].key() == r.key()) {
+                Some(g) => g.push(r),
+                None => group.push(vec![r]),
+            }
+        }
+
+        for g in cmps {
+            output.extend(self.render_comparison(g)?);
+        }
+
+        for g in indexes {
+            output.extend(self.render_index(g)?);
         }
+
+        Ok(output)
     }
 
-    Ok(output)
-}
+    fn render_operator(&self, r: RenderedOperator) -> syn::Result<TokenStream> {
+        let (tr, method) = match r.op {
+            Operator::Add => ("Add", "add"),
+            Operator::Sub => ("Sub", "sub"),
+            Operator::Mul => ("Mul", "mul"),
+            Operator::Div => ("Div", "div"),
+            Operator::Rem => ("Rem", "rem"),
+            Operator::BitAnd => ("BitAnd", "bitand"),
+            Operator::BitOr => ("BitOr", "bitor"),
+            Operator::BitXor => ("BitXor", "bitxor"),
+            Operator::Shl => ("Shl", "shl"),
+            Operator::Shr => ("Shr", "shr"),
+            Operator::Neg => ("Neg", "neg"),
+            Operator::Not | Operator::BitNot => ("Not", "not"),
+            Operator::AddAssign => ("AddAssign", "add_assign"),
+            Operator::SubAssign => ("SubAssign", "sub_assign"),
+            Operator::MulAssign => ("MulAssign", "mul_assign"),
+            Operator::DivAssign => ("DivAssign", "div_assign"),
+            Operator::RemAssign => ("RemAssign", "rem_assign"),
+            Operator::BitAndAssign => ("BitAndAssign", "bitand_assign"),
+            Operator::BitOrAssign => ("BitOrAssign", "bitor_assign"),
+            Operator::BitXorAssign => ("BitXorAssign", "bitxor_assign"),
+            Operator::ShlAssign => ("ShlAssign", "shl_assign"),
+            Operator::ShrAssign => ("ShrAssign", "shr_assign"),
+            op => {
+                return Err(Error::new_spanned(
+                    r.span,
+                    format_args!("operator{op} is not supported yet"),
+                ))
+            }
+        };
 
-fn render_class(item: Class) -> syn::Result<TokenStream> {
-    // Get metadata.
-    let class = item.name;
-    let name = class.to_string();
-    let meta = match META.get_type(&name) {
-        Some(v) => v,
-        None => {
-            return Err(Error::new_spanned(
-                class,
-                format_args!("cppbind::type_info<{name}> not found"),
-            ))
+        let tr = Ident::new(tr, Span::call_site());
+        let method = Ident::new(method, Span::call_site());
+        let class = &r.class;
+        let generics = r.generics();
+        let out = &r.call.out;
+        let expr = &r.call.expr;
+        let rhs = r.rhs.as_ref().map(|v| &v.ty);
+        let param = rhs.map(|t| quote!(rhs: #t));
+        let target = rhs.map(|t| quote!(<#t>));
+
+        // Compound assignment operators mutate the left operand in-place. Other operators produce a
+        // new value from borrowed operands.
+        if r.op.to_string().ends_with('=') {
+            if r.c {
+                return Err(Error::new_spanned(
+                    r.span,
+                    format_args!("operator{} must be non-const", r.op),
+                ));
+            }
+
+            return Ok(quote! {
+                impl<#(#generics),*> ::std::ops::#tr #target for #class<T> {
+                    fn #method(&mut self, #param) {
+                        #expr;
+                    }
+                }
+            });
         }
-    };
 
-    // Get object size.
-    let size = match meta.size {
-        Some(v) => v,
-        None => {
-            return Err(Error::new_spanned(
-                class,
-                format_args!("cppbind::type_info<{name}>::size not found"),
-            ));
+        let this = if r.c {
+            quote!(&#class<T>)
+        } else {
+            quote!(&mut #class<T>)
+        };
+
+        Ok(quote! {
+            impl<#(#generics),*> ::std::ops::#tr #target for #this {
+                type Output = #out;
+
+                fn #method(self, #param) -> Self::Output {
+                    #expr
+                }
+            }
+        })
+    }
+
+    fn render_comparison(&self, g: Vec<RenderedOperator>) -> syn::Result<TokenStream> {
+        let find = |op| g.iter().find(|r| r.op == op);
+        let first = &g[0];
+        let class = &first.class;
+        let generics = first.generics();
+        let rhs = &first.rhs.as_ref().unwrap().ty;
+        let mut output = TokenStream::new();
+
+        // Implement PartialEq.
+        let eq = find(Operator::Eq);
+        let ne = find(Operator::Ne);
+
+        if eq.is_some() || ne.is_some() {
+            let eq = match (eq, ne) {
+                (Some(v), _) => v.call.expr.clone(),
+                (None, Some(v)) => {
+                    let expr = &v.call.expr;
+
+                    quote!(!#expr)
+                }
+                (None, None) => unreachable!(),
+            };
+
+            let ne = ne.map(|v| {
+                let expr = &v.call.expr;
+
+                quote! {
+                    fn ne(&self, other: &#rhs) -> bool {
+                        #expr
+                    }
+                }
+            });
+
+            output.extend(quote! {
+                impl<#(#generics),*> ::std::cmp::PartialEq<#rhs> for #class<T> {
+                    fn eq(&self, other: &#rhs) -> bool {
+                        #eq
+                    }
+
+                    #ne
+                }
+            });
         }
-    };
 
-    // Get alignment.
-    let align = match meta.align {
-        Some(v) => v,
-        None => {
+        // Implement PartialOrd.
+        let ords = [
+            (Operator::Lt, "lt"),
+            (Operator::Gt, "gt"),
+            (Operator::Le, "le"),
+            (Operator::Ge, "ge"),
+        ];
+
+        if ords.iter().all(|(op, _)| find(*op).is_none()) {
+            return Ok(output);
+        }
+
+        if eq.is_none() && ne.is_none() {
             return Err(Error::new_spanned(
-                class,
-                format_args!("cppbind::type_info<{name}>::align not found"),
-            ))
+                &first.span,
+                "operator== is required to implement PartialOrd",
+            ));
         }
-    };
 
-    // Render constructor wrappers.
-    let mut impls = TokenStream::new();
+        // Less and greater can be tested with operator< alone when both operands are the same class.
+        let lt = match find(Operator::Lt) {
+            Some(v) => v,
+            None => {
+                return Err(Error::new_spanned(
+                    &first.span,
+                    "operator< is required to implement PartialOrd",
+                ))
+            }
+        };
+
+        let less = &lt.call.expr;
+        let greater = match find(Operator::Gt) {
+            Some(v) => v.call.expr.clone(),
+            None if lt.symmetric => {
+                let ffi = &lt.ffi;
 
-    for (i, ctor) in item.ctors.iter().enumerate() {
-        let name = format_ident!("new{}", i + 1, span = ctor.span);
-        let ffi = format_ident!("{}_ctor{}", class, i + 1, span = Span::call_site());
+                quote!(unsafe { #ffi(other.as_ptr().cast(), self.as_ptr().cast()) })
+            }
+            None => {
+                return Err(Error::new_spanned(
+                    &lt.span,
+                    "operator> is required to implement PartialOrd",
+                ))
+            }
+        };
 
-        impls.extend(quote! {
-            pub unsafe fn #name(mut this: T) -> Self {
-                #ffi(this.as_mut_ptr());
+        let methods = ords.into_iter().filter_map(|(op, name)| {
+            let expr = &find(op)?.call.expr;
+            let name = Ident::new(name, Span::call_site());
+
+            Some(quote! {
+                fn #name(&self, other: &#rhs) -> bool {
+                    #expr
+                }
+            })
+        });
 
-                Self {
-                    mem: this,
-                    phantom: ::std::marker::PhantomData,
+        output.extend(quote! {
+            impl<#(#generics),*> ::std::cmp::PartialOrd<#rhs> for #class<T> {
+                fn partial_cmp(&self, other: &#rhs) -> Option<::std::cmp::Ordering> {
+                    if #less {
+                        Some(::std::cmp::Ordering::Less)
+                    } else if #greater {
+                        Some(::std::cmp::Ordering::Greater)
+                    } else if self.eq(other) {
+                        Some(::std::cmp::Ordering::Equal)
+                    } else {
+                        None
+                    }
                 }
+
+                #(#methods)*
             }
         });
+
+        Ok(output)
     }
 
-    // Render constructor FFI.
-    let mut externs = TokenStream::new();
+    fn render_index(&self, g: Vec<RenderedOperator>) -> syn::Result<TokenStream> {
+        let index = g.iter().find(|r| r.c);
+        let index_mut = g.iter().find(|r| !r.c);
+        let first = &g[0];
+        let class = &first.class;
+        let generics = first.generics();
+        let idx = &first.rhs.as_ref().unwrap().ty;
+
+        // IndexMut requires Index.
+        let index = match index {
+            Some(v) => v,
+            None => {
+                return Err(Error::new_spanned(
+                    &first.span,
+                    "non-const operator[] requires a const overload to implement IndexMut",
+                ))
+            }
+        };
+
+        // The output must be a reference.
+        let output = match &index.call.pointee {
+            Some(v) => v,
+            None => {
+                return Err(Error::new_spanned(
+                    &index.span,
+                    "operator[] must return a reference to implement Index",
+                ))
+            }
+        };
+
+        let expr = &index.call.expr;
+        let mut impls = quote! {
+            impl<#(#generics),*> ::std::ops::Index<#idx> for #class<T> {
+                type Output = #output;
 
-    for (i, ctor) in item.ctors.iter().enumerate() {
-        // Build name.
-        let mut name = vec![Segment::Ident(name.as_str().into())];
+                fn index(&self, index: #idx) -> &Self::Output {
+                    unsafe { &*#expr }
+                }
+            }
+        };
 
-        name.push(Segment::Ctor);
+        if let Some(r) = index_mut {
+            if r.call.pointee.is_none() {
+                return Err(Error::new_spanned(
+                    &r.span,
+                    "operator[] must return a reference to implement IndexMut",
+                ));
+            }
 
-        // Build parameters.
-        let mut params = Vec::with_capacity(ctor.params.len());
+            let expr = &r.call.expr;
 
-        if ctor.params.is_empty() {
-            params.push(Type::Void);
-        } else {
-            todo!("parameterized constructor");
+            impls.extend(quote! {
+                impl<#(#generics),*> ::std::ops::IndexMut<#idx> for #class<T> {
+                    fn index_mut(&mut self, index: #idx) -> &mut Self::Output {
+                        unsafe { &mut *#expr }
+                    }
+                }
+            });
         }
 
-        // Render.
-        let sym = Symbol::new(Name::Nested(name), Some(Signature::new(params)));
-        let sym = sym.to_itanium();
-        let name = format_ident!("{}_ctor{}", class, i + 1, span = Span::call_site());
+        Ok(impls)
+    }
+
+    fn render_operand(
+        &self,
+        ns: &[Ident],
+        ty: &Type,
+        name: &Ident,
+        borrowed: bool,
+        span: &Ident,
+    ) -> syn::Result<Operand> {
+        // Check if reference to a class we know.
+        if let Type::Ref { c, t } = ty {
+            let class = match t.as_ref() {
+                Type::Class(v) => self.classes.get(&v.to_string()),
+                _ => None,
+            };
 
-        externs.extend(quote! {
-            unsafe extern "C-unwind" {
-                #[link_name = #sym]
-                fn #name(this: *mut ());
+            if let Some(path) = class {
+                let class = self.rust_path(ns, path);
+                let (ty, expr, ffi) = match (c, borrowed) {
+                    (true, true) => (
+                        quote!(#class<U>),
+                        quote!(#name.as_ptr().cast()),
+                        quote!(*const ()),
+                    ),
+                    (true, false) => (
+                        quote!(&#class<U>),
+                        quote!(#name.as_ptr().cast()),
+                        quote!(*const ()),
+                    ),
+                    (false, false) => (
+                        quote!(&mut #class<U>),
+                        quote!(#name.as_mut_ptr().cast()),
+                        quote!(*mut ()),
+                    ),
+                    (false, true) => {
+                        return Err(Error::new_spanned(
+                            span,
+                            format_args!("passing '{ty}' to this operator is not supported"),
+                        ))
+                    }
+                };
+
+                return Ok(Operand {
+                    generic: Some(quote!(U: ::cppbind::Memory<Class = #class<U>>)),
+                    ty,
+                    expr,
+                    ffi: quote!(#name: #ffi),
+                });
             }
-        });
+        }
+
+        // Constant reference to other types is passed by value on Rust side.
+        let ffi = self.render_type(ns, ty, Some(span))?;
+        let (ty, expr) = match ty {
+            Type::Ref { c: true, t } if !matches!(t.as_ref(), Type::Class(_)) => {
+                let t = self.render_type(ns, t, Some(span))?;
+
+                if borrowed {
+                    (t, quote!(#name))
+                } else {
+                    (t, quote!(&#name))
+                }
+            }
+            Type::Ref { .. } if borrowed => {
+                return Err(Error::new_spanned(
+                    span,
+                    format_args!("passing '{ty}' to this operator is not supported"),
+                ))
+            }
+            _ if borrowed => (ffi.clone(), quote!(*#name)),
+            _ => (ffi.clone(), quote!(#name)),
+        };
+
+        Ok(Operand {
+            generic: None,
+            ty,
+            expr,
+            ffi: quote!(#name: #ffi),
+        })
+    }
+
+    fn render_call(
+        &self,
+        ns: &[Ident],
+        ffi: &Ident,
+        args: &[TokenStream],
+        ret: &Type,
+        span: &Ident,
+    ) -> syn::Result<Call> {
+        // Class returned by value need to be constructed on the memory provided by the caller. This
+        // is always the case for the class we know unless it is trivial for the purposes of calls,
+        // which may be returned in registers depending on the types of its members that we don't
+        // know.
+        if let Type::Class(v) = ret {
+            if let Some(path) = self.classes.get(&v.to_string()) {
+                match META
+                    .get_type(v.to_string())
+                    .and_then(|t| t.trivial_for_calls)
+                {
+                    Some(false) => {}
+                    Some(true) => {
+                        return Err(Error::new_spanned(
+                            span,
+                            format_args!(
+                                "returning class '{v}' by value is not supported since it is trivial for the purposes of calls and may be returned in registers, return a reference or give it a non-trivial copy constructor, move constructor or destructor instead"
+                            ),
+                        ));
+                    }
+                    None => {
+                        return Err(Error::new_spanned(
+                            span,
+                            format_args!("cppbind::type_info<{v}>::trivial_for_calls not found"),
+                        ));
+                    }
+                }
+
+                let class = self.rust_path(ns, path);
+                let mut path = path.clone();
+                let name = path.pop().unwrap();
+
+                path.push(memory_name(&name));
+
+                let mem = self.rust_path(ns, &path);
+
+                return Ok(Call {
+                    out: quote!(#class<::cppbind::Heap<#mem>>),
+                    expr: quote! {{
+                        // The address of the return value is passed as the first argument on x86-64
+                        // but other architectures may pass it in a different register.
+                        #[cfg(not(target_arch = "x86_64"))]
+                        ::std::compile_error!("returning a class by value is only supported on x86-64");
+
+                        let mut r = ::cppbind::Heap::<#mem>::new();
+
+                        unsafe {
+                            #ffi(::cppbind::Memory::as_mut_ptr(&mut r), #(#args),*);
+                            #class::assume_init(r)
+                        }
+                    }},
+                    sret: quote!(ret: *mut (),),
+                    ret: TokenStream::new(),
+                    pointee: None,
+                });
+            }
+        }
+
+        // Other types.
+        let out = self.render_output(ns, ret, span)?;
+        let pointee = match ret {
+            Type::Ref { t, .. } if !matches!(t.as_ref(), Type::Class(_)) => {
+                Some(self.render_type(ns, t, Some(span))?)
+            }
+            _ => None,
+        };
+
+        Ok(Call {
+            ret: out.as_ref().map(|t| quote!(-> #t)).unwrap_or_default(),
+            out: out.unwrap_or_else(|| quote!(())),
+            expr: quote!(unsafe { #ffi(#(#args),*) }),
+            sret: TokenStream::new(),
+            pointee,
+        })
     }
 
-    // Generate destructor FFI.
-    let dtor = format_ident!("{}_dtor", class, span = Span::call_site());
-    let sym = Symbol::new(
-        Name::Nested(vec![Segment::Ident(name.as_str().into()), Segment::Dtor]),
-        Some(Signature::new(vec![Type::Void])),
-    )
-    .to_itanium();
+    fn render_params<'a>(
+        &self,
+        ns: &[Ident],
+        params: impl IntoIterator<Item = &'a Param>,
+    ) -> syn::Result<Params> {
+        let mut r = Params {
+            generics: Vec::new(),
+            args: Vec::new(),
+            exprs: Vec::new(),
+            ffi_args: Vec::new(),
+            types: Vec::new(),
+        };
+
+        for (i, p) in params.into_iter().enumerate() {
+            let ty = self.expand_alias(ns, resolve_type(ns, &p.ty));
+            let name = match &p.name {
+                Some(v) => rust_ident(v),
+                None => format_ident!("arg{}", i + 1),
+            };
+
+            // Check if std::function we know. An object passed by value is a temporary that is
+            // destroyed by the caller after the call.
+            let function = match &ty {
+                Type::Class(v) => self.functions.get(&v.to_string()).map(|v| (None, v)),
+                Type::Ref { c, t } => match t.as_ref() {
+                    Type::Class(v) => self.functions.get(&v.to_string()).map(|v| (Some(*c), v)),
+                    _ => None,
+                },
+                _ => None,
+            };
+
+            if let Some((c, path)) = function {
+                let f = self.rust_path(ns, path);
+
+                match c {
+                    None => {
+                        r.args.push(quote!(mut #name: #f));
+                        r.exprs.push(quote!(#name.as_mut_ptr().cast()));
+                        r.ffi_args.push(quote!(#name: *mut ()));
+                    }
+                    Some(true) => {
+                        r.args.push(quote!(#name: &#f));
+                        r.exprs.push(quote!(#name.as_ptr().cast()));
+                        r.ffi_args.push(quote!(#name: *const ()));
+                    }
+                    Some(false) => {
+                        r.args.push(quote!(#name: &mut #f));
+                        r.exprs.push(quote!(#name.as_mut_ptr().cast()));
+                        r.ffi_args.push(quote!(#name: *mut ()));
+                    }
+                }
+
+                r.types.push(ty);
+                continue;
+            }
+
+            // Check if reference to a class we know.
+            let class = match &ty {
+                Type::Ref { c, t } => match t.as_ref() {
+                    Type::Class(v) => self.classes.get(&v.to_string()).map(|v| (*c, v)),
+                    _ => None,
+                },
+                _ => None,
+            };
+
+            if let Some((c, path)) = class {
+                let class = self.rust_path(ns, path);
+                let generic = format_ident!("M{}", i + 1);
+
+                r.generics
+                    .push(quote!(#generic: ::cppbind::Memory<Class = #class<#generic>>));
+
+                if c {
+                    r.args.push(quote!(#name: &#class<#generic>));
+                    r.exprs.push(quote!(#name.as_ptr().cast()));
+                    r.ffi_args.push(quote!(#name: *const ()));
+                } else {
+                    r.args.push(quote!(#name: &mut #class<#generic>));
+                    r.exprs.push(quote!(#name.as_mut_ptr().cast()));
+                    r.ffi_args.push(quote!(#name: *mut ()));
+                }
+            } else {
+                let t = self.render_type(ns, &ty, p.name.as_ref())?;
+
+                r.args.push(quote!(#name: #t));
+                r.exprs.push(quote!(#name));
+                r.ffi_args.push(quote!(#name: #t));
+            }
 
-    externs.extend(quote! {
-        unsafe extern "C-unwind" {
-            #[link_name = #sym]
-            fn #dtor(this: *mut (), __in_chrg: ::std::ffi::c_int);
+            r.types.push(ty);
         }
-    });
 
-    // Compose.
-    let align = Literal::usize_unsuffixed(align);
-    let mem = if name.chars().next().unwrap().is_uppercase() {
-        format_ident!("{name}Memory")
-    } else {
-        format_ident!("{name}_memory")
-    };
+        Ok(r)
+    }
+
+    fn render_return(&self, ns: &[Ident], ty: &Type, span: &Ident) -> syn::Result<TokenStream> {
+        let ty = resolve_type(ns, ty);
+        let ty = self.render_output(ns, &ty, span)?;
+
+        Ok(ty.map(|t| quote!(-> #t)).unwrap_or_default())
+    }
 
-    Ok(quote! {
-        #[allow(non_camel_case_types)]
-        pub struct #class<T: ::cppbind::Memory<Class = Self>> {
-            mem: T,
-            phantom: ::std::marker::PhantomData<::std::rc::Rc<()>>,
+    /// Returns [`None`] if `ty` is `void`.
+    fn render_output(
+        &self,
+        ns: &[Ident],
+        ty: &Type,
+        span: &Ident,
+    ) -> syn::Result<Option<TokenStream>> {
+        if *ty == Type::Void {
+            return Ok(None);
         }
 
-        impl<T: ::cppbind::Memory<Class = Self>> #class<T> {
-            #impls
+        // Reference is returned as a pointer to prevent unbounded lifetime.
+        let ty = match ty {
+            Type::Ref { c, t } => Type::Ptr {
+                c: *c,
+                t: t.clone(),
+            },
+            t => t.clone(),
+        };
+
+        self.render_type(ns, &ty, Some(span)).map(Some)
+    }
+
+    fn render_type(
+        &self,
+        ns: &[Ident],
+        ty: &Type,
+        span: Option<&Ident>,
+    ) -> syn::Result<TokenStream> {
+        let ty = match ty {
+            Type::Void => quote!(::std::ffi::c_void),
+            Type::Bool => quote!(bool),
+            Type::Char => quote!(::std::ffi::c_char),
+            Type::Schar => quote!(::std::ffi::c_schar),
+            Type::Uchar => quote!(::std::ffi::c_uchar),
+            Type::Short => quote!(::std::ffi::c_short),
+            Type::Ushort => quote!(::std::ffi::c_ushort),
+            Type::Int => quote!(::std::ffi::c_int),
+            Type::Uint => quote!(::std::ffi::c_uint),
+            Type::Long => quote!(::std::ffi::c_long),
+            Type::Ulong => quote!(::std::ffi::c_ulong),
+            Type::Longlong => quote!(::std::ffi::c_longlong),
+            Type::Ulonglong => quote!(::std::ffi::c_ulonglong),
+            Type::Float => quote!(f32),
+            Type::Double => quote!(f64),
+            Type::Ptr { c: _, t } if matches!(t.as_ref(), Type::Func { .. }) => {
+                let (ret, params) = match t.as_ref() {
+                    Type::Func { ret, params } => (ret, params),
+                    _ => unreachable!(),
+                };
+
+                // Pointer to function is nullable. We use C since the function may be implemented in
+                // Rust, which must not unwind into C++.
+                let params = params
+                    .iter()
+                    .map(|t| self.render_type(ns, t, span))
+                    .collect::<syn::Result<Vec<_>>>()?;
+                let ret = match span {
+                    Some(v) => self.render_return(ns, ret, v)?,
+                    None => self.render_return(ns, ret, &Ident::new("fn", Span::call_site()))?,
+                };
+
+                quote!(Option<unsafe extern "C" fn(#(#params),*) #ret>)
+            }
+            Type::Ptr { c, t } => {
+                let t = self.render_pointee(ns, t, span)?;
+
+                if *c {
+                    quote!(*const #t)
+                } else {
+                    quote!(*mut #t)
+                }
+            }
+            Type::Ref { c, t } => {
+                let p = self.render_pointee(ns, t, span)?;
+
+                // Reference to a class is passed as a pointer.
+                match (t.as_ref(), c) {
+                    (Type::Class(_), true) => quote!(*const #p),
+                    (Type::Class(_), false) => quote!(*mut #p),
+                    (_, true) => quote!(&#p),
+                    (_, false) => quote!(&mut #p),
+                }
+            }
+            Type::RvalueRef { .. } | Type::Func { .. } | Type::Class(_) | Type::Param(_) => {
+                let msg = format!("passing '{ty}' is not supported yet");
+
+                return Err(match span {
+                    Some(v) => Error::new_spanned(v, msg),
+                    None => Error::new(Span::call_site(), msg),
+                });
+            }
+        };
+
+        Ok(ty)
+    }
+
+    /// Returns the path to the view type if `ty` is a reference to a class we know. The view is
+    /// mutable if the reference is non-const.
+    fn render_view(&self, ns: &[Ident], ty: &Type) -> Option<TokenStream> {
+        let (c, t) = match ty {
+            Type::Ref { c, t } => (*c, t),
+            _ => return None,
+        };
+
+        let mut path = match t.as_ref() {
+            Type::Class(v) => self.classes.get(&v.to_string())?.clone(),
+            _ => return None,
+        };
+
+        let class = path.pop().unwrap();
+
+        path.push(view_name(&class, !c));
+
+        Some(self.rust_path(ns, &path))
+    }
+
+    fn render_pointee(
+        &self,
+        ns: &[Ident],
+        ty: &Type,
+        span: Option<&Ident>,
+    ) -> syn::Result<TokenStream> {
+        // Pointer to a class we know will point to its memory type.
+        let name = match ty {
+            Type::Class(v) => v.to_string(),
+            t => return self.render_type(ns, t, span),
+        };
+
+        match self.classes.get(&name) {
+            Some(v) => {
+                let mut path = v.clone();
+                let class = path.pop().unwrap();
+
+                path.push(memory_name(&class));
+
+                Ok(self.rust_path(ns, &path))
+            }
+            None => Ok(quote!(::std::ffi::c_void)),
         }
+    }
+
+    /// Replaces an alias declared in [cpp](super::cpp) macro with its target.
+    fn expand_alias(&self, ns: &[Ident], ty: Type) -> Type {
+        match ty {
+            Type::Ref { c, t } => Type::Ref {
+                c,
+                t: self.expand_alias(ns, *t).into(),
+            },
+            Type::Class(n) => {
+                // Lookup from the inner most namespace.
+                for i in (0..=ns.len()).rev() {
+                    let mut name = ns[..i]
+                        .iter()
+                        .map(|v| Segment::Ident(v.to_string().into()))
+                        .collect::<Vec<_>>();
+
+                    name.extend(n.segments().iter().cloned());
+
+                    if let Some(v) = self.aliases.get(&Name::Nested(name).to_string()) {
+                        return v.clone();
+                    }
+                }
 
-        impl<T: ::cppbind::Memory<Class = Self>> Drop for #class<T> {
-            fn drop(&mut self) {
-                unsafe { #dtor(self.mem.as_mut_ptr(), 0) };
+                Type::Class(n)
             }
+            t => t,
         }
+    }
+
+    fn rust_path(&self, ns: &[Ident], path: &[Ident]) -> TokenStream {
+        let parents = ns.iter().map(|_| quote!(super::));
+
+        quote!(#(#parents)* #(#path)::*)
+    }
+}
+
+/// Overloaded operator with its operands resolved.
+///
+/// `lhs` is the first operand, which is an implicit object parameter for a member function. A class
+/// returned by value is constructed on the heap so `ret` cannot be a class that is trivial for the
+/// purposes of calls.
+struct Overload {
+    op: Operator,
+    span: Ident,
+    lhs: Type,
+    rhs: Vec<(Type, Option<Ident>)>,
+    ret: Type,
+    noexcept: bool,
+    sym: String,
+}
+
+/// Overloaded operator that was rendered to FFI.
+struct RenderedOperator {
+    op: Operator,
+    span: Ident,
+    c: bool,
+    class: TokenStream,
+    ffi: Ident,
+    rhs: Option<Operand>,
+    symmetric: bool,
+    call: Call,
+}
+
+impl RenderedOperator {
+    fn key(&self) -> (String, String) {
+        let rhs = self
+            .rhs
+            .as_ref()
+            .map(|v| v.ty.to_string())
+            .unwrap_or_default();
+
+        (self.class.to_string(), rhs)
+    }
+
+    fn generics(&self) -> Vec<TokenStream> {
+        let class = &self.class;
+        let rhs = self.rhs.as_ref().and_then(|v| v.generic.clone());
+
+        std::iter::once(quote!(T: ::cppbind::Memory<Class = #class<T>>))
+            .chain(rhs)
+            .collect()
+    }
+}
 
-        #[allow(non_camel_case_types)]
-        #[repr(C, align(#align))]
-        pub struct #mem {
-            data: [::std::mem::MaybeUninit<u8>; #size],
-            phantom: ::std::marker::PhantomPinned,
+/// Rendered operand of an operator.
+struct Operand {
+    generic: Option<TokenStream>,
+    ty: TokenStream,
+    expr: TokenStream,
+    ffi: TokenStream,
+}
+
+/// Rendered call to FFI function.
+///
+/// `pointee` is the referred type when the function returns a reference to a non-class type.
+struct Call {
+    out: TokenStream,
+    expr: TokenStream,
+    sret: TokenStream,
+    ret: TokenStream,
+    pointee: Option<TokenStream>,
+}
+
+/// Contains rendered parameters of a function.
+struct Params {
+    generics: Vec<TokenStream>,
+    args: Vec<TokenStream>,
+    exprs: Vec<TokenStream>,
+    ffi_args: Vec<TokenStream>,
+    types: Vec<Type>,
+}
+
+impl Params {
+    fn sig(&self, c: bool) -> Signature {
+        if self.types.is_empty() {
+            Signature::new(None, vec![Type::Void], c)
+        } else {
+            Signature::new(None, self.types.clone(), c)
         }
+    }
+}
+
+/// Returns fully qualified name of `class` that was declared inside `ns`.
+fn class_name(ns: &[Ident], class: &Class) -> Name<'static> {
+    let mut segments = ns
+        .iter()
+        .map(|v| Segment::Ident(v.to_string().into()))
+        .collect::<Vec<_>>();
+
+    segments.push(Segment::Ident(class.name.to_string().into()));
+    segments.extend(class.args.iter().map(|a| resolve_segment(ns, a)));
+
+    Name::Nested(segments)
+}
+
+/// Resolves all class names in `ty` that was written inside `ns` to fully qualified names.
+fn resolve_type(ns: &[Ident], ty: &Type) -> Type {
+    match ty {
+        Type::Ptr { c, t } => Type::Ptr {
+            c: *c,
+            t: resolve_type(ns, t).into(),
+        },
+        Type::Ref { c, t } => Type::Ref {
+            c: *c,
+            t: resolve_type(ns, t).into(),
+        },
+        Type::RvalueRef { c, t } => Type::RvalueRef {
+            c: *c,
+            t: resolve_type(ns, t).into(),
+        },
+        Type::Func { ret, params } => Type::Func {
+            ret: resolve_type(ns, ret).into(),
+            params: params.iter().map(|t| resolve_type(ns, t)).collect(),
+        },
+        Type::Class(n) => {
+            let segments = n
+                .segments()
+                .iter()
+                .map(|s| resolve_segment(ns, s))
+                .collect::<Vec<_>>();
+
+            // Lookup from the inner most namespace.
+            for i in (0..=ns.len()).rev() {
+                let mut name = ns[..i]
+                    .iter()
+                    .map(|v| Segment::Ident(v.to_string().into()))
+                    .collect::<Vec<_>>();
+
+                name.extend(segments.iter().cloned());
 
-        impl #mem {
-            pub const fn new() -> Self {
-                Self {
-                    data: [const { ::std::mem::MaybeUninit::uninit() }; #size],
-                    phantom: ::std::marker::PhantomPinned,
+                if let Some(v) = META.get_type(Name::Nested(name).to_string()) {
+                    return Type::Class(v.name.clone());
                 }
             }
+
+            Type::Class(Name::Nested(segments))
         }
+        t => t.clone(),
+    }
+}
 
-        impl Default for #mem {
-            fn default() -> Self {
-                Self::new()
+/// Replaces the names of template parameters in `ty` with [`Type::Param`].
+fn bind_params(ty: &Type, params: &[Ident]) -> Type {
+    match ty {
+        Type::Ptr { c, t } => Type::Ptr {
+            c: *c,
+            t: bind_params(t, params).into(),
+        },
+        Type::Ref { c, t } => Type::Ref {
+            c: *c,
+            t: bind_params(t, params).into(),
+        },
+        Type::RvalueRef { c, t } => Type::RvalueRef {
+            c: *c,
+            t: bind_params(t, params).into(),
+        },
+        Type::Func { ret, params: p } => Type::Func {
+            ret: bind_params(ret, params).into(),
+            params: p.iter().map(|t| bind_params(t, params)).collect(),
+        },
+        Type::Class(n) => {
+            if let [Segment::Ident(v)] = n.segments() {
+                if let Some(i) = params.iter().position(|p| *p == *v) {
+                    return Type::Param(i);
+                }
             }
+
+            let segments = n
+                .segments()
+                .iter()
+                .map(|s| match s {
+                    Segment::TemplateArg(TemplateArg::Type(t)) => {
+                        Segment::TemplateArg(TemplateArg::Type(bind_params(t, params).into()))
+                    }
+                    s => s.clone(),
+                })
+                .collect();
+
+            Type::Class(Name::Nested(segments))
         }
+        t => t.clone(),
+    }
+}
 
-        impl ::cppbind::HeapAlloc for #mem {
-            type Class = #class<::cppbind::Heap<Self>>;
+/// Substitutes template parameters in `ty` with `args`. Returns [`None`] if a type parameter
+/// is substituted with a literal.
+fn instantiate(ty: &Type, args: &[TemplateArg]) -> Option<Type> {
+    let ty = match ty {
+        Type::Ptr { c, t } => Type::Ptr {
+            c: *c,
+            t: instantiate(t, args)?.into(),
+        },
+        Type::Ref { c, t } => Type::Ref {
+            c: *c,
+            t: instantiate(t, args)?.into(),
+        },
+        Type::RvalueRef { c, t } => Type::RvalueRef {
+            c: *c,
+            t: instantiate(t, args)?.into(),
+        },
+        Type::Func { ret, params } => Type::Func {
+            ret: instantiate(ret, args)?.into(),
+            params: params
+                .iter()
+                .map(|t| instantiate(t, args))
+                .collect::<Option<_>>()?,
+        },
+        Type::Class(n) => {
+            let mut segments = Vec::with_capacity(n.segments().len());
 
-            fn alloc() -> *mut () {
-                unsafe { ::cppbind::new(#size) }
+            for s in n.segments() {
+                let s = match s {
+                    Segment::TemplateArg(TemplateArg::Type(t)) => match t.as_ref() {
+                        Type::Param(i) => Segment::TemplateArg(args.get(*i)?.clone()),
+                        t => Segment::TemplateArg(TemplateArg::Type(instantiate(t, args)?.into())),
+                    },
+                    s => s.clone(),
+                };
+
+                segments.push(s);
             }
 
-            unsafe fn dealloc(this: *mut ()) {
-                ::cppbind::delete(this, #size);
+            Type::Class(Name::Nested(segments))
+        }
+        Type::Param(i) => match args.get(*i)? {
+            TemplateArg::Type(t) => t.as_ref().clone(),
+            TemplateArg::Literal(_, _) => return None,
+        },
+        t => t.clone(),
+    };
+
+    Some(ty)
+}
+
+fn resolve_segment(ns: &[Ident], s: &Segment<'static>) -> Segment<'static> {
+    match s {
+        Segment::TemplateArg(TemplateArg::Type(t)) => {
+            Segment::TemplateArg(TemplateArg::Type(resolve_type(ns, t).into()))
+        }
+        s => s.clone(),
+    }
+}
+
+/// Returns the name of Rust type for `class`.
+fn rust_name(class: &Class) -> Ident {
+    if class.args.is_empty() {
+        return class.name.clone();
+    }
+
+    // Build a name from template arguments (e.g. Matrix<float, 4> become Matrix_float_4).
+    let mut name = class.name.to_string();
+
+    for a in &class.args {
+        let a = match a {
+            Segment::TemplateArg(v) => v.to_string(),
+            _ => unreachable!(),
+        };
+
+        name.push('_');
+
+        for c in a.replace('*', "_ptr").replace('&', "_ref").chars() {
+            if c.is_ascii_alphanumeric() {
+                name.push(c);
+            } else if c == '-' {
+                name.push_str("neg");
+            } else if !name.ends_with('_') {
+                name.push('_');
             }
         }
 
-        impl ::cppbind::Memory for ::std::pin::Pin<&mut #mem> {
-            type Class = #class<Self>;
+        while name.ends_with('_') {
+            name.pop();
+        }
+    }
+
+    Ident::new(&name, class.name.span())
+}
+
+/// Returns the name of memory type for a Rust type `class`.
+fn memory_name(class: &Ident) -> Ident {
+    if class.to_string().chars().next().unwrap().is_uppercase() {
+        format_ident!("{class}Memory")
+    } else {
+        format_ident!("{class}_memory")
+    }
+}
+
+/// Converts a C++ class name to snake case (e.g. `IOStream` become `io_stream`).
+fn snake_case(name: &str) -> String {
+    let chars = name.chars().collect::<Vec<_>>();
+    let mut r = String::with_capacity(name.len());
 
-            fn as_mut_ptr(&mut self) -> *mut () {
-                unsafe { self.data.as_mut().get_unchecked_mut().as_mut_ptr().cast() }
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_uppercase() {
+            let prev = i.checked_sub(1).map(|i| chars[i]);
+            let next = chars.get(i + 1);
+            let lower = prev.is_some_and(|p| p.is_lowercase() || p.is_ascii_digit());
+            let acronym =
+                prev.is_some_and(|p| p.is_uppercase()) && next.is_some_and(|n| n.is_lowercase());
+
+            if (lower || acronym) && !r.ends_with('_') {
+                r.push('_');
             }
+
+            r.extend(c.to_lowercase());
+        } else {
+            r.push(c);
+        }
+    }
+
+    r
+}
+
+/// Returns `true` if `ty` is `size_t`.
+fn is_size_t(ty: &Type) -> bool {
+    match ty {
+        Type::Ulong => true,
+        Type::Class(v) => matches!(v.to_string().as_str(), "size_t" | "std::size_t"),
+        _ => false,
+    }
+}
+
+/// Returns `true` if `ty` is `const std::nothrow_t&`.
+fn is_nothrow(ty: &Type) -> bool {
+    match ty {
+        Type::Ref { c: true, t } => {
+            matches!(t.as_ref(), Type::Class(v) if v.to_string() == "std::nothrow_t")
         }
+        _ => false,
+    }
+}
+
+/// Returns the name of borrowed view for a Rust type `class`.
+fn view_name(class: &Ident, mutable: bool) -> Ident {
+    let upper = class.to_string().chars().next().unwrap().is_uppercase();
+
+    match (upper, mutable) {
+        (true, false) => format_ident!("{class}Ref"),
+        (true, true) => format_ident!("{class}Mut"),
+        (false, false) => format_ident!("{class}_ref"),
+        (false, true) => format_ident!("{class}_mut"),
+    }
+}
+
+fn trait_name(class: &Ident) -> Ident {
+    if class.to_string().chars().next().unwrap().is_uppercase() {
+        format_ident!("{class}Impl")
+    } else {
+        format_ident!("{class}_impl")
+    }
+}
+
+fn adapter_name(class: &Ident) -> Ident {
+    if class.to_string().chars().next().unwrap().is_uppercase() {
+        format_ident!("{class}Adapter")
+    } else {
+        format_ident!("{class}_adapter")
+    }
+}
+
+/// Returns ABI of an imported function. A `noexcept` function never unwind so the call does not need
+/// a landing pad.
+fn abi(noexcept: bool) -> Literal {
+    Literal::string(if noexcept { "C" } else { "C-unwind" })
+}
 
-        #externs
-    })
+/// Returns Rust identifier for C++ identifier `v`.
+fn rust_ident(v: &Ident) -> Ident {
+    // Use raw identifier if the name is a Rust keyword.
+    if syn::parse2::<Ident>(quote!(#v)).is_err() {
+        Ident::new_raw(&v.to_string(), v.span())
+    } else {
+        v.clone()
+    }
 }
 
 /// Contains C++ declarations parsed from [cpp](super::cpp) macro.
@@ -207,6 +3548,22 @@ impl Parse for Declarations {
 
             if l.peek(kw::class) {
                 items.push(Declaration::Class(input.parse()?));
+            } else if l.peek(kw::namespace) {
+                items.push(Declaration::Namespace(input.parse()?));
+            } else if l.peek(kw::template) {
+                items.push(Declaration::Function(input.parse()?));
+            } else if l.peek(kw::using) {
+                items.push(Declaration::Alias(input.parse()?));
+            } else if input.fork().parse::<Type>().is_ok() {
+                let ret = input.parse()?;
+
+                if !input.peek(kw::operator) {
+                    return Err(input.error("expect 'operator'"));
+                }
+
+                items.push(Declaration::Operator(OperatorFunction::parse_rest(
+                    input, ret, false,
+                )?));
             } else {
                 return Err(l.error());
             }
@@ -219,9 +3576,23 @@ impl Parse for Declarations {
 /// Single C++ declaration.
 enum Declaration {
     Class(Class),
+    Alias(Alias),
+    Function(Function),
+    Operator(OperatorFunction),
+    Namespace(Namespace),
 }
 
 mod kw {
     syn::custom_keyword!(class);
+    syn::custom_keyword!(delete);
+    syn::custom_keyword!(namespace);
+    syn::custom_keyword!(new);
+    syn::custom_keyword!(noexcept);
+    syn::custom_keyword!(operator);
+    syn::custom_keyword!(private);
+    syn::custom_keyword!(protected);
     syn::custom_keyword!(public);
+    syn::custom_keyword!(template);
+    syn::custom_keyword!(typename);
+    syn::custom_keyword!(using);
 }
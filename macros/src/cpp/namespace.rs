@@ -0,0 +1,35 @@
+use super::{kw, Declarations};
+use syn::ext::IdentExt;
+use syn::parse::{Parse, ParseStream};
+use syn::{braced, Ident, Token};
+
+/// C++ namespace declaration.
+pub struct Namespace {
+    pub names: Vec<Ident>,
+    pub items: Declarations,
+}
+
+impl Parse for Namespace {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        // Skip declaration.
+        input.parse::<kw::namespace>()?;
+
+        // Parse name. We also support nested namespace definition from C++17.
+        let mut names = vec![input.call(Ident::parse_any)?];
+
+        while input.peek(Token![::]) {
+            input.parse::<Token![::]>()?;
+            names.push(input.call(Ident::parse_any)?);
+        }
+
+        // Parse body.
+        let body;
+
+        braced!(body in input);
+
+        Ok(Self {
+            names,
+            items: body.parse()?,
+        })
+    }
+}
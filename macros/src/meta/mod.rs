@@ -1,12 +1,16 @@
 pub use self::ty::*;
 
 use crate::symbol::{Name, Segment, Symbol, TemplateArg};
+use crate::ty::Type;
 use memmap2::Mmap;
 use object::read::archive::ArchiveFile;
 use object::read::elf::ElfFile64;
 use object::read::macho::MachOFile64;
-use object::{Endianness, LittleEndian, Object, ObjectSection, ObjectSymbol, SymbolIndex};
+use object::{
+    Architecture, Endianness, LittleEndian, Object, ObjectSection, ObjectSymbol, SymbolIndex,
+};
 use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
 use std::fs::File;
 use std::path::Path;
 use thiserror::Error;
@@ -15,7 +19,7 @@ mod ty;
 
 /// Contains C++ metadata loaded from a static library.
 pub struct Metadata {
-    types: HashMap<String, TypeInfo>,
+    types: HashMap<String, TypeInfo>, // Key is a fully qualified name without inline namespaces.
 }
 
 impl Metadata {
@@ -26,9 +30,7 @@ impl Metadata {
         let ar = ArchiveFile::parse(file.as_ref()).map_err(MetadataError::ParseFileFailed)?;
 
         // Parse members.
-        let mut meta = Self {
-            types: HashMap::new(),
-        };
+        let mut defs = Vec::new();
 
         for (i, mem) in ar.members().enumerate() {
             // Get member data.
@@ -46,17 +48,17 @@ impl Metadata {
                     Err(e) => return Err(MetadataError::ParseMemberFailed(name.into_owned(), e)),
                 };
 
-                meta.parse_obj(obj)
+                Self::parse_obj(&mut defs, &name, obj)
             } else if data.starts_with(&0xFEEDFACFu32.to_le_bytes()) {
                 let obj = match MachOFile64::<LittleEndian>::parse(data) {
                     Ok(v) => v,
                     Err(e) => return Err(MetadataError::ParseMemberFailed(name.into_owned(), e)),
                 };
 
-                meta.parse_obj(obj)
+                Self::parse_obj(&mut defs, &name, obj)
             } else {
                 return Err(MetadataError::UnknownMember(
-                    data.iter().take(4).map(|v| *v).collect(),
+                    data.iter().take(4).copied().collect(),
                 ));
             };
 
@@ -65,32 +67,130 @@ impl Metadata {
             }
         }
 
+        Self::from_definitions(defs)
+    }
+
+    fn from_definitions(defs: Vec<Definition>) -> Result<Self, MetadataError> {
+        // Check for ODR violations. The same metadata can be defined in multiple members (e.g.
+        // COMDAT) but it must have the same value otherwise the class was seen differently by some
+        // translation units.
+        let mut meta = Self {
+            types: HashMap::new(),
+        };
+        let mut sources = HashMap::<(&str, Option<String>, Info), &Definition>::new();
+
+        for def in &defs {
+            use std::collections::hash_map::Entry;
+
+            match sources.entry((&def.key, def.arg.as_ref().map(|a| a.to_string()), def.info)) {
+                Entry::Occupied(e) => {
+                    let prev = e.get();
+
+                    if prev.value == def.value {
+                        continue;
+                    }
+
+                    return Err(MetadataError::ConflictedDefinition(
+                        def.symbol(),
+                        prev.member.clone(),
+                        prev.value,
+                        def.member.clone(),
+                        def.value,
+                    ));
+                }
+                Entry::Vacant(e) => e.insert(def),
+            };
+
+            // Populate TypeInfo. The name of std::function from function_info does not have the
+            // inline namespace so prefer the one from the other templates.
+            let info = meta
+                .types
+                .entry(def.key.clone())
+                .or_insert_with(|| TypeInfo::new(def.class.clone()));
+
+            if !matches!(def.info, Info::Construct | Info::Destroy) {
+                info.name = def.class.clone();
+            }
+
+            match def.info {
+                Info::Size => info.size = Some(def.value),
+                Info::Align => info.align = Some(def.value),
+                Info::TriviallyCopyable => info.trivially_copyable = Some(def.value != 0),
+                Info::TriviallyMoveConstructible => {
+                    info.trivially_move_constructible = Some(def.value != 0)
+                }
+                Info::TriviallyDestructible => info.trivially_destructible = Some(def.value != 0),
+                Info::TrivialForCalls => info.trivial_for_calls = Some(def.value != 0),
+                Info::NewAlign => info.new_align = Some(def.value),
+                Info::AlignedNew => info.aligned_new = Some(def.value != 0),
+                Info::SizedDelete => info.sized_delete = Some(def.value != 0),
+                Info::Polymorphic => info.polymorphic = Some(def.value != 0),
+                Info::VirtualDestructor => info.virtual_destructor = Some(def.value != 0),
+                Info::Offset => {
+                    let base = def.arg.as_ref().unwrap().to_string();
+
+                    info.bases.insert(base, def.value);
+                }
+                Info::Deleter => info.deleter = true,
+                Info::Cast => {
+                    let base = def.arg.as_ref().unwrap().to_string();
+
+                    info.virtual_bases.insert(base);
+                }
+                Info::Member => {
+                    let method = match def.arg.as_ref().unwrap().segments().last() {
+                        Some(Segment::Ident(v)) => v.to_string(),
+                        _ => unreachable!(),
+                    };
+
+                    info.virtuals.insert(method, def.value.checked_sub(1));
+                }
+                Info::Thunk => {
+                    let method = match def.arg.as_ref().unwrap().segments().last() {
+                        Some(Segment::Ident(v)) => v.to_string(),
+                        _ => unreachable!(),
+                    };
+
+                    info.throwing.insert(method);
+                }
+                Info::Construct => info.construct = true,
+                Info::Destroy => info.destroy = true,
+            }
+        }
+
         Ok(meta)
     }
 
+    /// Looks up a class by its fully qualified name. The inline namespace of the standard library
+    /// can be omitted (e.g. `std::function` for `std::__1::function` on libc++).
     pub fn get_type(&self, name: impl AsRef<str>) -> Option<&TypeInfo> {
-        self.types.get(name.as_ref())
+        self.types.get(key(name.as_ref()).as_str())
     }
 
-    fn parse_obj<'a>(&mut self, obj: impl Object<'a>) -> Result<(), ObjectError> {
+    fn parse_obj<'a>(
+        defs: &mut Vec<Definition>,
+        member: &str,
+        obj: impl Object<'a>,
+    ) -> Result<(), ObjectError> {
         // Parse symbols.
         for sym in obj.symbols() {
             let index = sym.index();
 
-            self.parse_sym(&obj, sym)
-                .map_err(|e| ObjectError::ParseSymbolFailed(index, e))?;
+            match Self::parse_sym(member, &obj, sym) {
+                Ok(Some(v)) => defs.push(v),
+                Ok(None) => {}
+                Err(e) => return Err(ObjectError::ParseSymbolFailed(index, e)),
+            }
         }
 
         Ok(())
     }
 
     fn parse_sym<'a>(
-        &mut self,
+        member: &str,
         obj: &impl Object<'a>,
         sym: impl ObjectSymbol<'a>,
-    ) -> Result<(), SymbolError> {
-        use std::collections::hash_map::Entry;
-
+    ) -> Result<Option<Definition>, SymbolError> {
         // Get symbol name.
         let index = sym.index();
         let raw = match sym.name_bytes() {
@@ -101,7 +201,7 @@ impl Metadata {
         // Get section index.
         let section = match sym.section_index() {
             Some(v) => v,
-            None => return Ok(()),
+            None => return Ok(None),
         };
 
         // Parse name.
@@ -109,69 +209,358 @@ impl Metadata {
         let len: usize = sym.size().try_into().unwrap();
         let sym = match Symbol::parse(raw) {
             Ok(v) => v,
-            Err(_) => return Ok(()), // Ignore unknown symbol.
+            Err(_) => return Ok(None), // Ignore unknown symbol.
+        };
+
+        let (class, arg, info) = match Self::parse_name(&sym)? {
+            Some(v) => v,
+            None => return Ok(None),
         };
 
-        // Check namespace.
+        // Get section data.
+        let section = obj
+            .section_by_index(section)
+            .map_err(|e| SymbolError::GetSectionFailed(index, e))?;
+        let section = section
+            .data()
+            .map_err(|e| SymbolError::GetSectionDataFailed(index, e))?;
+
+        // Get value. The value is either size_t or bool. A function does not have a value, neither a
+        // function pointer since it need a relocation.
+        let read = |off: usize, len: usize| {
+            section
+                .get(off..(off + len))
+                .and_then(|v| match v {
+                    [v] => Some(usize::from(*v)),
+                    v => v.try_into().ok().map(usize::from_ne_bytes),
+                })
+                .ok_or(SymbolError::GetDataFailed(index))
+        };
+
+        let value = match info {
+            Info::Deleter | Info::Cast | Info::Thunk | Info::Construct | Info::Destroy => 0,
+            Info::Member => {
+                let ptr = read(off, 8)?;
+                let adj = read(off + 8, 8)?;
+
+                decode_member(obj.architecture(), ptr, adj)
+            }
+            _ => read(off, len)?,
+        };
+
+        Ok(Some(Definition {
+            member: member.to_owned(),
+            key: key(&class.to_string()),
+            class,
+            arg,
+            info,
+            value,
+        }))
+    }
+
+    /// Parses the name of a metadata symbol. Returns [`None`] if the symbol is not a metadata.
+    fn parse_name(
+        sym: &Symbol,
+    ) -> Result<Option<(Name<'static>, Option<Name<'static>>, Info)>, SymbolError> {
+        // Check namespace. A special name is always generated by the compiler.
+        if sym.special().is_some() {
+            return Ok(None);
+        }
+
         let mut iter = match sym.name() {
             Name::Nested(v) => v.iter(),
-            _ => return Ok(()),
+            _ => return Ok(None),
         };
 
         if !iter
             .next()
             .is_some_and(|v| *v == Segment::Ident("cppbind".into()))
         {
-            return Ok(());
+            return Ok(None);
         }
 
-        // Check if type_info.
-        if !iter
-            .next()
-            .is_some_and(|v| *v == Segment::Ident("type_info".into()))
-        {
-            return Err(SymbolError::UnknownCppbindSymbol);
-        }
+        // Check if type_info, base_info, virtual_info, throwing_info or function_info.
+        let kind = match iter.next() {
+            Some(Segment::Ident(v)) => v.as_ref(),
+            _ => return Err(SymbolError::UnknownCppbindSymbol),
+        };
 
-        // Get class name.
-        let class = iter.next().ok_or(SymbolError::UnknownCppbindSymbol)?;
-        let class = match class {
-            Segment::TemplateArg(TemplateArg::Ident(v)) => v,
+        let kind = match kind {
+            "detail" => return Ok(None),
+            "type_info" => Template::Type,
+            "base_info" => Template::Base,
+            "virtual_info" => Template::Virtual,
+            "throwing_info" => Template::Throwing,
+            "function_info" => Template::Function,
             _ => return Err(SymbolError::UnknownCppbindSymbol),
         };
 
-        // Get TypeInfo.
-        let info = match self.types.entry(class.as_ref().to_owned()) {
-            Entry::Occupied(e) => e.into_mut(),
-            Entry::Vacant(e) => e.insert(TypeInfo::default()),
+        // Get class name. The function_info is keyed by std::function with the same signature.
+        let class = if kind == Template::Function {
+            match iter.next() {
+                Some(Segment::TemplateArg(TemplateArg::Type(v)))
+                    if matches!(v.as_ref(), Type::Func { .. }) =>
+                {
+                    Name::Nested(vec![
+                        Segment::Ident("std".into()),
+                        Segment::Ident("function".into()),
+                        Segment::TemplateArg(TemplateArg::Type(v.clone())),
+                    ])
+                }
+                _ => return Err(SymbolError::UnknownCppbindSymbol),
+            }
+        } else {
+            Self::parse_class(iter.next())?
+        };
+
+        let arg = match kind {
+            Template::Type | Template::Function => None,
+            Template::Base => Some(Self::parse_class(iter.next())?),
+            Template::Virtual | Template::Throwing => {
+                // The tag must be cppbind::method::NAME.
+                let tag = Self::parse_class(iter.next())?;
+                let valid = match tag.segments() {
+                    [n, m, Segment::Ident(_)] => {
+                        *n == Segment::Ident("cppbind".into())
+                            && *m == Segment::Ident("method".into())
+                    }
+                    _ => false,
+                };
+
+                if !valid {
+                    return Err(SymbolError::UnknownCppbindSymbol);
+                }
+
+                Some(tag)
+            }
         };
 
         // Check info type.
         let ty = iter.next().ok_or(SymbolError::UnknownCppbindSymbol)?;
-        let section = obj
-            .section_by_index(section)
-            .map_err(|e| SymbolError::GetSectionFailed(index, e))?;
-        let section = section
-            .data()
-            .map_err(|e| SymbolError::GetSectionDataFailed(index, e))?;
 
-        if *ty == Segment::Ident("size".into()) {
-            info.size = section
-                .get(off..(off + len))
-                .map(|v| usize::from_ne_bytes(v.try_into().unwrap()))
-                .ok_or_else(|| SymbolError::GetDataFailed(index))
-                .map(Some)?;
+        let info = if kind == Template::Base {
+            if *ty == Segment::Ident("offset".into()) {
+                Info::Offset
+            } else if *ty == Segment::Ident("cast".into()) {
+                Info::Cast
+            } else {
+                return Err(SymbolError::UnknownCppbindSymbol);
+            }
+        } else if kind == Template::Virtual {
+            if *ty == Segment::Ident("member".into()) {
+                Info::Member
+            } else {
+                return Err(SymbolError::UnknownCppbindSymbol);
+            }
+        } else if kind == Template::Function {
+            if *ty == Segment::Ident("construct".into()) {
+                Info::Construct
+            } else if *ty == Segment::Ident("destroy".into()) {
+                Info::Destroy
+            } else {
+                return Err(SymbolError::UnknownCppbindSymbol);
+            }
+        } else if kind == Template::Throwing {
+            if *ty == Segment::Ident("thunk".into()) {
+                Info::Thunk
+            } else {
+                return Err(SymbolError::UnknownCppbindSymbol);
+            }
+        } else if *ty == Segment::Ident("size".into()) {
+            Info::Size
         } else if *ty == Segment::Ident("align".into()) {
-            info.align = section
-                .get(off..(off + len))
-                .map(|v| usize::from_ne_bytes(v.try_into().unwrap()))
-                .ok_or_else(|| SymbolError::GetDataFailed(index))
-                .map(Some)?;
+            Info::Align
+        } else if *ty == Segment::Ident("trivially_copyable".into()) {
+            Info::TriviallyCopyable
+        } else if *ty == Segment::Ident("trivially_move_constructible".into()) {
+            Info::TriviallyMoveConstructible
+        } else if *ty == Segment::Ident("trivially_destructible".into()) {
+            Info::TriviallyDestructible
+        } else if *ty == Segment::Ident("trivial_for_calls".into()) {
+            Info::TrivialForCalls
+        } else if *ty == Segment::Ident("new_align".into()) {
+            Info::NewAlign
+        } else if *ty == Segment::Ident("aligned_new".into()) {
+            Info::AlignedNew
+        } else if *ty == Segment::Ident("sized_delete".into()) {
+            Info::SizedDelete
+        } else if *ty == Segment::Ident("polymorphic".into()) {
+            Info::Polymorphic
+        } else if *ty == Segment::Ident("virtual_destructor".into()) {
+            Info::VirtualDestructor
+        } else if *ty == Segment::Ident("deleter".into()) {
+            Info::Deleter
         } else {
             return Err(SymbolError::UnknownCppbindSymbol);
+        };
+
+        Ok(Some((class, arg, info)))
+    }
+
+    fn parse_class(seg: Option<&Segment>) -> Result<Name<'static>, SymbolError> {
+        match seg {
+            Some(Segment::TemplateArg(TemplateArg::Type(v))) => match v.as_ref() {
+                Type::Class(v) => Ok(v.clone()),
+                _ => Err(SymbolError::UnknownCppbindSymbol),
+            },
+            _ => Err(SymbolError::UnknownCppbindSymbol),
         }
+    }
+}
 
-        Ok(())
+/// Definition of a metadata from a member of the static library.
+struct Definition {
+    member: String,
+    key: String,
+    class: Name<'static>,
+    arg: Option<Name<'static>>,
+    info: Info,
+    value: usize,
+}
+
+impl Definition {
+    fn symbol(&self) -> String {
+        match (&self.arg, self.info) {
+            (Some(a), Info::Member) => {
+                format!(
+                    "cppbind::virtual_info<{}, {}>::{}",
+                    self.class, a, self.info
+                )
+            }
+            (Some(a), Info::Thunk) => {
+                format!(
+                    "cppbind::throwing_info<{}, {}>::{}",
+                    self.class, a, self.info
+                )
+            }
+            (Some(a), _) => format!("cppbind::base_info<{}, {}>::{}", self.class, a, self.info),
+            (None, Info::Construct | Info::Destroy) => match self.class.segments().last() {
+                Some(Segment::TemplateArg(s)) => {
+                    format!("cppbind::function_info<{}>::{}", s, self.info)
+                }
+                _ => unreachable!(),
+            },
+            (None, _) => format!("cppbind::type_info<{}>::{}", self.class, self.info),
+        }
+    }
+}
+
+/// Decodes Itanium pointer-to-member function. Returns 1 + the vtable offset if the member is
+/// virtual, otherwise zero.
+///
+/// The generic ABI stores 1 + the vtable offset in `ptr` for a virtual function. ARM-style ABI
+/// stores the vtable offset in `ptr` and indicates a virtual function with the lowest bit of `adj`
+/// since the lowest bit of a function address is used for Thumb.
+fn decode_member(arch: Architecture, ptr: usize, adj: usize) -> usize {
+    match arch {
+        Architecture::Aarch64
+        | Architecture::Aarch64_Ilp32
+        | Architecture::Arm
+        | Architecture::Mips
+        | Architecture::Mips64
+        | Architecture::Mips64_N32
+        | Architecture::Wasm32
+        | Architecture::Wasm64 => {
+            if adj & 1 != 0 {
+                ptr + 1
+            } else {
+                0
+            }
+        }
+        _ => {
+            if ptr & 1 != 0 {
+                ptr
+            } else {
+                0
+            }
+        }
+    }
+}
+
+/// Removes inline namespaces of the standard library from a fully qualified name (e.g.
+/// `std::__1::function` on libc++ or `std::__cxx11::basic_string` on libstdc++).
+fn key(name: &str) -> String {
+    let mut key = String::with_capacity(name.len());
+    let mut rest = name;
+
+    while let Some(i) = rest.find("std::__") {
+        let (head, tail) = rest.split_at(i);
+        let ident = |c: char| c.is_ascii_alphanumeric() || c == '_';
+        let top = !head.ends_with(|c: char| ident(c) || c == ':');
+        let tail = &tail[7..];
+        let len = tail.find(|c: char| !ident(c)).unwrap_or(tail.len());
+
+        key.push_str(head);
+        key.push_str("std::");
+
+        rest = match tail[len..].strip_prefix("::") {
+            Some(v) if top => v,
+            _ => {
+                key.push_str("__");
+                tail
+            }
+        };
+    }
+
+    key.push_str(rest);
+    key
+}
+
+/// Template of a metadata.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Template {
+    Type,
+    Base,
+    Virtual,
+    Throwing,
+    Function,
+}
+
+/// Type of metadata.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Info {
+    Size,
+    Align,
+    TriviallyCopyable,
+    TriviallyMoveConstructible,
+    TriviallyDestructible,
+    TrivialForCalls,
+    NewAlign,
+    AlignedNew,
+    SizedDelete,
+    Polymorphic,
+    VirtualDestructor,
+    Deleter,
+    Offset,
+    Cast,
+    Member,
+    Thunk,
+    Construct,
+    Destroy,
+}
+
+impl Display for Info {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Size => f.write_str("size"),
+            Self::Align => f.write_str("align"),
+            Self::TriviallyCopyable => f.write_str("trivially_copyable"),
+            Self::TriviallyMoveConstructible => f.write_str("trivially_move_constructible"),
+            Self::TriviallyDestructible => f.write_str("trivially_destructible"),
+            Self::TrivialForCalls => f.write_str("trivial_for_calls"),
+            Self::NewAlign => f.write_str("new_align"),
+            Self::AlignedNew => f.write_str("aligned_new"),
+            Self::SizedDelete => f.write_str("sized_delete"),
+            Self::Polymorphic => f.write_str("polymorphic"),
+            Self::VirtualDestructor => f.write_str("virtual_destructor"),
+            Self::Deleter => f.write_str("deleter"),
+            Self::Offset => f.write_str("offset"),
+            Self::Cast => f.write_str("cast"),
+            Self::Member => f.write_str("member"),
+            Self::Thunk => f.write_str("thunk"),
+            Self::Construct => f.write_str("construct"),
+            Self::Destroy => f.write_str("destroy"),
+        }
     }
 }
 
@@ -201,6 +590,9 @@ pub enum MetadataError {
 
     #[error("couldn't parse {0}")]
     ParseObjectFailed(String, #[source] ObjectError),
+
+    #[error("{0} has different values between {1} ({2}) and {3} ({4})")]
+    ConflictedDefinition(String, String, usize, String, usize),
 }
 
 /// Represents an error when [`Metadata`] fails to parse an object file.
@@ -228,3 +620,219 @@ pub enum SymbolError {
     #[error("couldn't get data of symbol #{0}")]
     GetDataFailed(SymbolIndex),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn def(member: &str, info: Info, value: usize) -> Definition {
+        let class = Name::Unscoped(Segment::Ident("Foo".into()));
+
+        Definition {
+            member: member.into(),
+            key: key(&class.to_string()),
+            class,
+            arg: None,
+            info,
+            value,
+        }
+    }
+
+    #[test]
+    fn duplicated_definition() {
+        let defs = vec![
+            def("a.o", Info::Size, 8),
+            def("b.o", Info::Size, 8),
+            def("b.o", Info::Align, 4),
+        ];
+        let meta = Metadata::from_definitions(defs).unwrap();
+        let info = meta.get_type("Foo").unwrap();
+
+        assert_eq!(info.size, Some(8));
+        assert_eq!(info.align, Some(4));
+    }
+
+    #[test]
+    fn conflicted_definition() {
+        let defs = vec![def("a.o", Info::Size, 8), def("b.o", Info::Size, 16)];
+
+        match Metadata::from_definitions(defs) {
+            Err(MetadataError::ConflictedDefinition(sym, a, 8, b, 16)) => {
+                assert_eq!(sym, "cppbind::type_info<Foo>::size");
+                assert_eq!(a, "a.o");
+                assert_eq!(b, "b.o");
+            }
+            _ => panic!("unexpected result"),
+        }
+    }
+
+    #[test]
+    fn inline_namespace() {
+        assert_eq!(
+            key("std::__1::function<void(const Event&)>"),
+            "std::function<void(const Event&)>"
+        );
+        assert_eq!(
+            key("Holder<std::__cxx11::basic_string<char>>"),
+            "Holder<std::basic_string<char>>"
+        );
+        assert_eq!(key("std::__detail"), "std::__detail");
+        assert_eq!(key("ns::std::__1::Foo"), "ns::std::__1::Foo");
+
+        let sym =
+            Symbol::parse("_ZN7cppbind9type_infoINSt3__18functionIFvRK5EventEEEE4sizeE").unwrap();
+        let class = Metadata::parse_class(sym.name().segments().get(2)).unwrap();
+        let defs = vec![Definition {
+            member: "a.o".into(),
+            key: key(&class.to_string()),
+            class,
+            arg: None,
+            info: Info::Size,
+            value: 32,
+        }];
+        let meta = Metadata::from_definitions(defs).unwrap();
+        let info = meta.get_type("std::function<void(const Event&)>").unwrap();
+
+        assert_eq!(info.size, Some(32));
+        assert_eq!(
+            info.name.to_string(),
+            "std::__1::function<void(const Event&)>"
+        );
+        assert!(meta.get_type(info.name.to_string()).is_some());
+    }
+
+    fn parse(mangled: &str, value: usize) -> Definition {
+        let sym = Symbol::parse(mangled).unwrap();
+        let (class, arg, info) = Metadata::parse_name(&sym).unwrap().unwrap();
+
+        Definition {
+            member: "a.o".into(),
+            key: key(&class.to_string()),
+            class,
+            arg,
+            info,
+            value,
+        }
+    }
+
+    #[test]
+    fn metadata_name() {
+        let def = parse("_ZN7cppbind9type_infoI5ShapeE22trivially_destructibleE", 0);
+
+        assert_eq!(def.key, "Shape");
+        assert_eq!(def.info, Info::TriviallyDestructible);
+
+        let def = parse("_ZN7cppbind9type_infoI6MatrixIfLm4EEE11aligned_newE", 0);
+
+        assert_eq!(def.key, "Matrix<float, 4>");
+        assert_eq!(def.info, Info::AlignedNew);
+
+        let def = parse("_ZN7cppbind9type_infoI5PointE17trivial_for_callsE", 0);
+
+        assert_eq!(def.key, "Point");
+        assert_eq!(def.info, Info::TrivialForCalls);
+
+        let def = parse("_ZN7cppbind9type_infoI5ShapeE7deleterEPv", 0);
+
+        assert_eq!(def.info, Info::Deleter);
+
+        let def = parse("_ZN7cppbind9base_infoI3Dog5NamedE6offsetE", 0);
+
+        assert_eq!(def.key, "Dog");
+        assert_eq!(def.arg.unwrap().to_string(), "Named");
+        assert_eq!(def.info, Info::Offset);
+
+        let def = parse(
+            "_ZN7cppbind12virtual_infoI5ShapeNS_6method4kindEE6memberE",
+            0,
+        );
+
+        assert_eq!(def.key, "Shape");
+        assert_eq!(def.arg.unwrap().to_string(), "cppbind::method::kind");
+        assert_eq!(def.info, Info::Member);
+
+        let def = parse(
+            "_ZN7cppbind13throwing_infoI6ParserNS_6method5parseEE5thunkE",
+            0,
+        );
+
+        assert_eq!(def.key, "Parser");
+        assert_eq!(def.arg.unwrap().to_string(), "cppbind::method::parse");
+        assert_eq!(def.info, Info::Thunk);
+
+        let def = parse(
+            "_ZN7cppbind13function_infoIFvRK5EventEE9constructEPvPFvS3_S6_ES6_PFvS6_E",
+            0,
+        );
+
+        assert_eq!(def.key, "std::function<void(const Event&)>");
+        assert_eq!(def.info, Info::Construct);
+
+        let def = parse("_ZN7cppbind13function_infoIFvRK5EventEE7destroyEPv", 0);
+
+        assert_eq!(def.key, "std::function<void(const Event&)>");
+        assert_eq!(def.info, Info::Destroy);
+    }
+
+    #[test]
+    fn unknown_name() {
+        let name = |v: &str| Metadata::parse_name(&Symbol::parse(v).unwrap());
+
+        assert!(matches!(name("_ZN2ns3FooE"), Ok(None)));
+        assert!(matches!(name("_ZN7cppbind6detail5errorC1Ev"), Ok(None)));
+        assert!(matches!(
+            name("_ZN7cppbind9type_infoI5ShapeE3fooE"),
+            Err(SymbolError::UnknownCppbindSymbol)
+        ));
+        assert!(matches!(
+            name("_ZN7cppbind12virtual_infoI5Shape5sidesE6memberE"),
+            Err(SymbolError::UnknownCppbindSymbol)
+        ));
+        assert!(matches!(
+            name("_ZN7cppbind13throwing_infoI6ParserNS_6method5parseEE6memberE"),
+            Err(SymbolError::UnknownCppbindSymbol)
+        ));
+    }
+
+    #[test]
+    fn populate() {
+        let defs = vec![
+            parse("_ZN7cppbind9type_infoI5ShapeE22trivially_destructibleE", 0),
+            parse("_ZN7cppbind9type_infoI5ShapeE11aligned_newE", 1),
+            parse(
+                "_ZN7cppbind12virtual_infoI5ShapeNS_6method4kindEE6memberE",
+                17,
+            ),
+            parse(
+                "_ZN7cppbind13throwing_infoI6ParserNS_6method5parseEE5thunkE",
+                0,
+            ),
+            parse("_ZN7cppbind13function_infoIFvRK5EventEE7destroyEPv", 0),
+        ];
+        let meta = Metadata::from_definitions(defs).unwrap();
+        let shape = meta.get_type("Shape").unwrap();
+
+        assert_eq!(shape.trivially_destructible, Some(false));
+        assert_eq!(shape.aligned_new, Some(true));
+        assert_eq!(shape.virtuals.get("kind"), Some(&Some(16)));
+        assert!(meta.get_type("Parser").unwrap().throwing.contains("parse"));
+
+        let func = meta.get_type("std::function<void(const Event&)>").unwrap();
+
+        assert!(func.destroy);
+        assert!(!func.construct);
+    }
+
+    #[test]
+    fn member_pointer() {
+        // Generic ABI.
+        assert_eq!(decode_member(Architecture::X86_64, 17, 0), 17);
+        assert_eq!(decode_member(Architecture::X86_64, 0, 0), 0);
+
+        // ARM-style ABI.
+        assert_eq!(decode_member(Architecture::Aarch64, 16, 1), 17);
+        assert_eq!(decode_member(Architecture::Aarch64, 0, 1), 1);
+        assert_eq!(decode_member(Architecture::Aarch64, 0x1001, 0), 0);
+        assert_eq!(decode_member(Architecture::Arm, 8, 3), 9);
+    }
+}
@@ -1,6 +1,51 @@
+use crate::symbol::Name;
+use std::collections::{HashMap, HashSet};
+
 /// Contains information for a C++ class.
-#[derive(Default)]
 pub struct TypeInfo {
+    pub name: Name<'static>,
     pub size: Option<usize>,
     pub align: Option<usize>,
+    pub trivially_copyable: Option<bool>,
+    pub trivially_move_constructible: Option<bool>,
+    pub trivially_destructible: Option<bool>,
+    pub trivial_for_calls: Option<bool>,
+    pub new_align: Option<usize>,
+    pub aligned_new: Option<bool>,
+    pub sized_delete: Option<bool>,
+    pub polymorphic: Option<bool>,
+    pub virtual_destructor: Option<bool>,
+    pub deleter: bool,
+    pub construct: bool,               // Only available on std::function.
+    pub destroy: bool,                 // Only available on std::function.
+    pub bases: HashMap<String, usize>, // Key is a fully qualified name of the base class.
+    pub virtual_bases: HashSet<String>,
+    pub virtuals: HashMap<String, Option<usize>>, // Key is a method name, value is vtable offset.
+    pub throwing: HashSet<String>,                // Method names that have a try/catch thunk.
+}
+
+impl TypeInfo {
+    pub fn new(name: Name<'static>) -> Self {
+        Self {
+            name,
+            size: None,
+            align: None,
+            trivially_copyable: None,
+            trivially_move_constructible: None,
+            trivially_destructible: None,
+            trivial_for_calls: None,
+            new_align: None,
+            aligned_new: None,
+            sized_delete: None,
+            polymorphic: None,
+            virtual_destructor: None,
+            deleter: false,
+            construct: false,
+            destroy: false,
+            bases: HashMap::new(),
+            virtual_bases: HashSet::new(),
+            virtuals: HashMap::new(),
+            throwing: HashSet::new(),
+        }
+    }
 }
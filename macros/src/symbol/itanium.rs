@@ -1,31 +1,125 @@
-use super::{Name, Segment, Symbol, SymbolError, TemplateArg};
+use super::{CtorKind, DtorKind, Name, Segment, Special, Symbol, SymbolError, TemplateArg};
+use crate::ty::Type;
 use std::cmp::min;
+use std::fmt::Write;
 use std::iter::Peekable;
+use std::ops::Range;
 use std::slice::Iter;
 
 pub fn parse(mangled: &[u8]) -> Result<Symbol<'static>, SymbolError> {
     let mut iter = mangled.iter().peekable();
+    let mut subs = Vec::new();
+
+    // Check if special name. Only a special name for a class is supported.
+    let special = if iter.next_if_eq(&&b'T').is_some() {
+        match iter.next() {
+            Some(b'I') => Some(Special::TypeInfo),
+            Some(b'S') => Some(Special::TypeName),
+            _ => return Err(SymbolError::UnknownSymbol),
+        }
+    } else {
+        None
+    };
+
     let name = match *iter.next().ok_or(SymbolError::UnknownSymbol)? {
-        b'N' => Name::Nested(parse_nested_name(&mut iter)?),
+        b'N' => Name::Nested(parse_nested_name(&mut iter, &mut subs)?),
+        b @ b'1'..=b'9' if special.is_some() => {
+            Name::Unscoped(Segment::Ident(parse_source_name(&mut iter, b)?.into()))
+        }
         _ => return Err(SymbolError::UnknownSymbol),
     };
 
-    Ok(Symbol { name, sig: None })
+    if special.is_some() && iter.next().is_some() {
+        return Err(SymbolError::UnknownSymbol);
+    }
+
+    Ok(Symbol {
+        special,
+        name,
+        sig: None,
+    })
+}
+
+pub fn mangle(out: &mut String, sym: &Symbol) {
+    let mut m = Mangler {
+        out,
+        subs: Vec::new(),
+    };
+
+    // Build special name.
+    if let Some(s) = sym.special {
+        match s {
+            Special::TypeInfo => m.out.push_str("TI"),
+            Special::TypeName => m.out.push_str("TS"),
+        }
+
+        m.push_name(sym.name.segments(), false);
+        return;
+    }
+
+    // Build name.
+    let c = sym.sig.as_ref().is_some_and(|s| s.c);
+
+    m.push_name(sym.name.segments(), c);
+
+    // Build signature.
+    if let Some(s) = &sym.sig {
+        if let Some(t) = &s.ret {
+            m.push_type(t);
+        }
+
+        for p in &s.params {
+            m.push_type(p);
+        }
+    }
 }
 
-fn parse_nested_name(iter: &mut Peekable<Iter<u8>>) -> Result<Vec<Segment<'static>>, SymbolError> {
+fn parse_nested_name(
+    iter: &mut Peekable<Iter<u8>>,
+    subs: &mut Vec<Candidate>,
+) -> Result<Vec<Segment<'static>>, SymbolError> {
     let mut segments = Vec::new();
+    let mut candidate = false;
 
     loop {
         let b = *iter.next().ok_or(SymbolError::UnknownSymbol)?;
 
+        // Every prefix except the name itself is a substitution candidate.
+        if candidate && b != b'E' {
+            subs.push(Candidate::Type(Type::Class(Name::Nested(segments.clone()))));
+        }
+
         match b {
             b'0' => return Err(SymbolError::UnknownSymbol), // Identifier with zero length?
             b'1'..=b'9' => segments.push(Segment::Ident(parse_source_name(iter, b)?.into())),
-            b'I' => parse_template_args(&mut segments, iter)?,
+            b'I' => parse_template_args(&mut segments, iter, subs)?,
+            b'C' if !segments.is_empty() => segments.push(match iter.next() {
+                Some(b'1') => Segment::Ctor(CtorKind::Complete),
+                Some(b'2') => Segment::Ctor(CtorKind::Base),
+                _ => return Err(SymbolError::UnknownSymbol),
+            }),
+            b'D' if !segments.is_empty() => segments.push(match iter.next() {
+                Some(b'0') => Segment::Dtor(DtorKind::Deleting),
+                Some(b'1') => Segment::Dtor(DtorKind::Complete),
+                Some(b'2') => Segment::Dtor(DtorKind::Base),
+                _ => return Err(SymbolError::UnknownSymbol),
+            }),
+            b'S' if segments.is_empty() => {
+                // Substitution is not a candidate.
+                segments = if iter.next_if_eq(&&b't').is_some() {
+                    vec![Segment::Ident("std".into())]
+                } else {
+                    into_segments(parse_substitution(iter, subs)?)?
+                };
+
+                candidate = false;
+                continue;
+            }
             b'E' => break,
             _ => return Err(SymbolError::UnknownSymbol),
         }
+
+        candidate = true;
     }
 
     Ok(segments)
@@ -59,17 +153,15 @@ fn parse_source_name(iter: &mut Peekable<Iter<u8>>, first: u8) -> Result<String,
 fn parse_template_args(
     segments: &mut Vec<Segment>,
     iter: &mut Peekable<Iter<u8>>,
+    subs: &mut Vec<Candidate>,
 ) -> Result<(), SymbolError> {
     loop {
         let b = *iter.next().ok_or(SymbolError::UnknownSymbol)?;
         let a = match b {
             b'E' => break,
-            b'0' => return Err(SymbolError::UnknownSymbol), // Identifier with zero length?
-            b'1'..=b'9' => TemplateArg::Ident(parse_source_name(iter, b)?.into()),
-            b'X' => todo!("expression"),
-            b'L' => todo!("simple expressions"),
-            b'J' => todo!("argument pack"),
-            _ => todo!(),
+            b'L' => parse_literal(iter)?,
+            b'X' | b'J' => return Err(SymbolError::UnknownSymbol),
+            b => TemplateArg::Type(parse_type(iter, b, subs)?.into()),
         };
 
         segments.push(Segment::TemplateArg(a));
@@ -77,3 +169,624 @@ fn parse_template_args(
 
     Ok(())
 }
+
+fn parse_literal(iter: &mut Peekable<Iter<u8>>) -> Result<TemplateArg, SymbolError> {
+    // Get type.
+    let b = *iter.next().ok_or(SymbolError::UnknownSymbol)?;
+    let ty = parse_builtin(b).ok_or(SymbolError::UnknownSymbol)?;
+
+    // Get value.
+    let neg = iter.next_if_eq(&&b'n').is_some();
+    let mut val = 0i128;
+
+    loop {
+        let b = *iter.next().ok_or(SymbolError::UnknownSymbol)?;
+
+        if b == b'E' {
+            break;
+        } else if !b.is_ascii_digit() {
+            return Err(SymbolError::UnknownSymbol);
+        }
+
+        val = val
+            .checked_mul(10)
+            .and_then(|v| v.checked_add((b - b'0').into()))
+            .ok_or(SymbolError::UnknownSymbol)?;
+    }
+
+    Ok(TemplateArg::Literal(
+        ty.into(),
+        if neg { -val } else { val },
+    ))
+}
+
+fn parse_type(
+    iter: &mut Peekable<Iter<u8>>,
+    first: u8,
+    subs: &mut Vec<Candidate>,
+) -> Result<Type, SymbolError> {
+    // Check if builtin type.
+    if let Some(v) = parse_builtin(first) {
+        return Ok(v);
+    }
+
+    // Parse class name.
+    let mut candidate = true;
+    let mut segments = match first {
+        b'0' => return Err(SymbolError::UnknownSymbol), // Identifier with zero length?
+        b'1'..=b'9' => vec![Segment::Ident(parse_source_name(iter, first)?.into())],
+        b'N' => {
+            let ty = Type::Class(Name::Nested(parse_nested_name(iter, subs)?));
+
+            subs.push(Candidate::Type(ty.clone()));
+
+            return Ok(ty);
+        }
+        b'F' => {
+            let b = *iter.next().ok_or(SymbolError::UnknownSymbol)?;
+            let ret = parse_type(iter, b, subs)?;
+            let mut params = Vec::new();
+
+            loop {
+                let b = *iter.next().ok_or(SymbolError::UnknownSymbol)?;
+
+                if b == b'E' {
+                    break;
+                }
+
+                params.push(parse_type(iter, b, subs)?);
+            }
+
+            if params == [Type::Void] {
+                params.clear();
+            }
+
+            let ty = Type::Func {
+                ret: ret.into(),
+                params,
+            };
+
+            subs.push(Candidate::Type(ty.clone()));
+
+            return Ok(ty);
+        }
+        b'P' | b'R' | b'O' => {
+            let (t, c) = parse_qualified_type(iter, subs)?;
+            let t = Box::new(t);
+            let ty = match first {
+                b'P' => Type::Ptr { c, t },
+                b'R' => Type::Ref { c, t },
+                _ => Type::RvalueRef { c, t },
+            };
+
+            subs.push(Candidate::Type(ty.clone()));
+
+            return Ok(ty);
+        }
+        b'S' if iter.next_if_eq(&&b't').is_some() => {
+            let b = *iter.next().ok_or(SymbolError::UnknownSymbol)?;
+
+            if !b.is_ascii_digit() || b == b'0' {
+                return Err(SymbolError::UnknownSymbol);
+            }
+
+            std_name(parse_source_name(iter, b)?)
+        }
+        b'S' => {
+            let ty = match parse_substitution(iter, subs)? {
+                Candidate::Type(v) => v,
+                Candidate::Const(_) => return Err(SymbolError::UnknownSymbol),
+            };
+
+            // The substitution itself is not a candidate but its template-id is.
+            if iter.peek().is_none_or(|&&b| b != b'I') {
+                return Ok(ty);
+            }
+
+            candidate = false;
+            into_segments(Candidate::Type(ty))?
+        }
+        _ => return Err(SymbolError::UnknownSymbol),
+    };
+
+    // Parse template arguments.
+    if iter.next_if_eq(&&b'I').is_some() {
+        if candidate {
+            let name = Type::Class(Name::Nested(segments.clone()));
+
+            subs.push(Candidate::Type(name));
+        }
+
+        parse_template_args(&mut segments, iter, subs)?;
+    }
+
+    // Construct the type.
+    let ty = Type::Class(if segments.len() == 1 {
+        Name::Unscoped(segments.pop().unwrap())
+    } else {
+        Name::Nested(segments)
+    });
+
+    subs.push(Candidate::Type(ty.clone()));
+
+    Ok(ty)
+}
+
+fn parse_qualified_type(
+    iter: &mut Peekable<Iter<u8>>,
+    subs: &mut Vec<Candidate>,
+) -> Result<(Type, bool), SymbolError> {
+    let b = *iter.next().ok_or(SymbolError::UnknownSymbol)?;
+
+    match b {
+        b'K' => {
+            let b = *iter.next().ok_or(SymbolError::UnknownSymbol)?;
+            let ty = parse_type(iter, b, subs)?;
+
+            subs.push(Candidate::Const(ty.clone()));
+
+            Ok((ty, true))
+        }
+        b'S' => {
+            // A substitution of const-qualified type cannot be resolved by parse_type.
+            let mut fork = iter.clone();
+
+            match parse_substitution(&mut fork, subs) {
+                Ok(Candidate::Const(v)) if fork.peek().is_none_or(|&&b| b != b'I') => {
+                    *iter = fork;
+                    Ok((v, true))
+                }
+                _ => Ok((parse_type(iter, b, subs)?, false)),
+            }
+        }
+        b => Ok((parse_type(iter, b, subs)?, false)),
+    }
+}
+
+fn parse_builtin(b: u8) -> Option<Type> {
+    let ty = match b {
+        b'v' => Type::Void,
+        b'b' => Type::Bool,
+        b'c' => Type::Char,
+        b'a' => Type::Schar,
+        b'h' => Type::Uchar,
+        b's' => Type::Short,
+        b't' => Type::Ushort,
+        b'i' => Type::Int,
+        b'j' => Type::Uint,
+        b'l' => Type::Long,
+        b'm' => Type::Ulong,
+        b'x' => Type::Longlong,
+        b'y' => Type::Ulonglong,
+        b'f' => Type::Float,
+        b'd' => Type::Double,
+        _ => return None,
+    };
+
+    Some(ty)
+}
+
+fn parse_substitution(
+    iter: &mut Peekable<Iter<u8>>,
+    subs: &[Candidate],
+) -> Result<Candidate, SymbolError> {
+    // Check for abbreviations.
+    let abbr = match iter.peek() {
+        Some(b'a') => Some("allocator"),
+        Some(b'b') => Some("basic_string"),
+        Some(b's' | b'i' | b'o' | b'd') => return Err(SymbolError::UnknownSymbol),
+        _ => None,
+    };
+
+    if let Some(v) = abbr {
+        iter.next();
+
+        return Ok(Candidate::Type(Type::Class(Name::Nested(std_name(v)))));
+    }
+
+    // Get index.
+    let mut i = None::<usize>;
+
+    loop {
+        let b = *iter.next().ok_or(SymbolError::UnknownSymbol)?;
+        let d = match b {
+            b'_' => break,
+            b'0'..=b'9' => b - b'0',
+            b'A'..=b'Z' => b - b'A' + 10,
+            _ => return Err(SymbolError::UnknownSymbol),
+        };
+
+        i = i
+            .unwrap_or(0)
+            .checked_mul(36)
+            .and_then(|v| v.checked_add(d.into()))
+            .map(Some)
+            .ok_or(SymbolError::UnknownSymbol)?;
+    }
+
+    // Get substitution.
+    let i = match i {
+        Some(v) => v.checked_add(1).ok_or(SymbolError::UnknownSymbol)?,
+        None => 0,
+    };
+
+    subs.get(i).cloned().ok_or(SymbolError::UnknownSymbol)
+}
+
+fn into_segments(sub: Candidate) -> Result<Vec<Segment<'static>>, SymbolError> {
+    match sub {
+        Candidate::Type(Type::Class(Name::Nested(v))) => Ok(v),
+        Candidate::Type(Type::Class(Name::Unscoped(v))) => Ok(vec![v]),
+        _ => Err(SymbolError::UnknownSymbol),
+    }
+}
+
+fn std_name(name: impl Into<String>) -> Vec<Segment<'static>> {
+    vec![
+        Segment::Ident("std".into()),
+        Segment::Ident(name.into().into()),
+    ]
+}
+
+/// Substitution candidate.
+#[derive(Clone, PartialEq)]
+enum Candidate {
+    Type(Type),
+    Const(Type),
+}
+
+/// Encoder for a symbol name.
+struct Mangler<'a> {
+    out: &'a mut String,
+    subs: Vec<Candidate>,
+}
+
+impl Mangler<'_> {
+    fn push_name(&mut self, segments: &[Segment], c: bool) {
+        // Split into components. Consecutive template arguments are a single component.
+        let mut parts = Vec::<Range<usize>>::new();
+
+        for (i, s) in segments.iter().enumerate() {
+            match parts.last_mut() {
+                Some(p)
+                    if matches!(s, Segment::TemplateArg(_))
+                        && matches!(segments[i - 1], Segment::TemplateArg(_)) =>
+                {
+                    p.end += 1
+                }
+                _ => parts.push(i..(i + 1)),
+            }
+        }
+
+        // Check if we need a nested name.
+        let std = segments.first() == Some(&Segment::Ident("std".into()));
+        let names = parts
+            .iter()
+            .filter(|p| !matches!(segments[p.start], Segment::TemplateArg(_)))
+            .count();
+        let nested = c || !(names == 1 || (names == 2 && std));
+
+        if nested {
+            self.out.push('N');
+
+            if c {
+                self.out.push('K');
+            }
+        }
+
+        // Find the longest prefix that can be substituted.
+        let mut next = 0;
+
+        for (i, p) in parts.iter().enumerate().take(parts.len() - 1).rev() {
+            let prefix = segments[..p.end].iter().map(Segment::to_static).collect();
+            let prefix = Type::Class(Name::Nested(prefix));
+
+            if let Some(s) = self.find(&Candidate::Type(prefix)) {
+                self.push_substitution(s);
+                next = i + 1;
+                break;
+            }
+        }
+
+        if next == 0 && std {
+            // std::allocator and std::basic_string have their own abbreviation.
+            let abbr = match segments.get(1) {
+                Some(Segment::Ident(v)) if v == "allocator" => Some("Sa"),
+                Some(Segment::Ident(v)) if v == "basic_string" => Some("Sb"),
+                _ => None,
+            };
+
+            match abbr {
+                Some(v) if parts.len() > 2 => {
+                    self.out.push_str(v);
+                    next = 2;
+                }
+                _ => {
+                    self.out.push_str("St");
+                    next = 1;
+                }
+            }
+        }
+
+        // Push remaining components.
+        for (i, p) in parts.iter().enumerate().skip(next) {
+            match &segments[p.start] {
+                Segment::Ident(v) => write!(self.out, "{}{}", v.len(), v).unwrap(),
+                Segment::TemplateArg(_) => {
+                    self.out.push('I');
+
+                    for s in &segments[p.clone()] {
+                        if let Segment::TemplateArg(v) = s {
+                            self.push_template_arg(v);
+                        }
+                    }
+
+                    self.out.push('E');
+                }
+                Segment::Ctor(CtorKind::Complete) => self.out.push_str("C1"),
+                Segment::Ctor(CtorKind::Base) => self.out.push_str("C2"),
+                Segment::Dtor(DtorKind::Deleting) => self.out.push_str("D0"),
+                Segment::Dtor(DtorKind::Complete) => self.out.push_str("D1"),
+                Segment::Dtor(DtorKind::Base) => self.out.push_str("D2"),
+                Segment::New => self.out.push_str("nw"),
+                Segment::Delete => self.out.push_str("dl"),
+                Segment::Operator(v) => self.out.push_str(v.code()),
+            }
+
+            // Every prefix except the name itself is a substitution candidate.
+            if i != parts.len() - 1 {
+                let prefix = segments[..p.end].iter().map(Segment::to_static).collect();
+                let prefix = Type::Class(Name::Nested(prefix));
+
+                self.subs.push(Candidate::Type(prefix));
+            }
+        }
+
+        if nested {
+            self.out.push('E');
+        }
+    }
+
+    fn push_template_arg(&mut self, arg: &TemplateArg) {
+        match arg {
+            TemplateArg::Type(v) => self.push_type(v),
+            TemplateArg::Literal(t, v) => {
+                self.out.push('L');
+                self.push_type(t);
+
+                if *v < 0 {
+                    self.out.push('n');
+                }
+
+                write!(self.out, "{}E", v.unsigned_abs()).unwrap();
+            }
+        }
+    }
+
+    fn push_type(&mut self, ty: &Type) {
+        match ty {
+            Type::Void => self.out.push('v'),
+            Type::Bool => self.out.push('b'),
+            Type::Char => self.out.push('c'),
+            Type::Schar => self.out.push('a'),
+            Type::Uchar => self.out.push('h'),
+            Type::Short => self.out.push('s'),
+            Type::Ushort => self.out.push('t'),
+            Type::Int => self.out.push('i'),
+            Type::Uint => self.out.push('j'),
+            Type::Long => self.out.push('l'),
+            Type::Ulong => self.out.push('m'),
+            Type::Longlong => self.out.push('x'),
+            Type::Ulonglong => self.out.push('y'),
+            Type::Float => self.out.push('f'),
+            Type::Double => self.out.push('d'),
+            Type::Ptr { c, t } => self.push_candidate(ty, |m| {
+                m.out.push('P');
+                m.push_pointee(*c, t);
+            }),
+            Type::Ref { c, t } => self.push_candidate(ty, |m| {
+                m.out.push('R');
+                m.push_pointee(*c, t);
+            }),
+            Type::RvalueRef { c, t } => self.push_candidate(ty, |m| {
+                m.out.push('O');
+                m.push_pointee(*c, t);
+            }),
+            Type::Func { ret, params } => self.push_candidate(ty, |m| {
+                m.out.push('F');
+                m.push_type(ret);
+
+                if params.is_empty() {
+                    m.out.push('v');
+                }
+
+                for p in params {
+                    m.push_type(p);
+                }
+
+                m.out.push('E');
+            }),
+            Type::Class(v) => self.push_candidate(ty, |m| m.push_name(v.segments(), false)),
+            Type::Param(i) => self.push_candidate(ty, |m| m.push_seq_id('T', *i)),
+        }
+    }
+
+    /// Encodes a type that is a substitution candidate with `f`. A builtin type is never a
+    /// candidate.
+    fn push_candidate(&mut self, ty: &Type, f: impl FnOnce(&mut Self)) {
+        let sub = Candidate::Type(ty.clone());
+
+        if let Some(i) = self.find(&sub) {
+            self.push_substitution(i);
+            return;
+        }
+
+        f(self);
+        self.subs.push(sub);
+    }
+
+    fn push_pointee(&mut self, c: bool, ty: &Type) {
+        if c {
+            self.push_const(ty);
+        } else {
+            self.push_type(ty);
+        }
+    }
+
+    fn push_const(&mut self, ty: &Type) {
+        let sub = Candidate::Const(ty.clone());
+
+        if let Some(i) = self.find(&sub) {
+            self.push_substitution(i);
+            return;
+        }
+
+        self.out.push('K');
+        self.push_type(ty);
+        self.subs.push(sub);
+    }
+
+    fn push_substitution(&mut self, i: usize) {
+        self.push_seq_id('S', i);
+    }
+
+    fn push_seq_id(&mut self, prefix: char, i: usize) {
+        self.out.push(prefix);
+
+        if i != 0 {
+            let mut i = i - 1;
+            let mut seq = Vec::new();
+
+            loop {
+                seq.push(char::from(b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ"[i % 36]));
+                i /= 36;
+
+                if i == 0 {
+                    break;
+                }
+            }
+
+            self.out.extend(seq.into_iter().rev());
+        }
+
+        self.out.push('_');
+    }
+
+    fn find(&self, sub: &Candidate) -> Option<usize> {
+        self.subs.iter().position(|v| v == sub)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::Signature;
+    use super::*;
+
+    fn roundtrip(mangled: &str) -> Symbol<'static> {
+        let sym = Symbol::parse(mangled).unwrap();
+
+        assert_eq!(sym.to_itanium(), format!("\u{1}{mangled}"));
+
+        sym
+    }
+
+    fn class(names: &[&'static str]) -> Type {
+        Type::Class(Name::Nested(
+            names.iter().map(|&v| Segment::Ident(v.into())).collect(),
+        ))
+    }
+
+    #[test]
+    fn literal() {
+        let sym = roundtrip("_ZN3NegILin1EE1vE");
+
+        assert_eq!(sym.name().to_string(), "Neg<-1>::v");
+
+        let sym = roundtrip("_ZN4FlagILb1EE1vE");
+
+        assert_eq!(sym.name().to_string(), "Flag<true>::v");
+
+        let sym = roundtrip("_ZN3MaxILy18446744073709551615EE1vE");
+
+        assert_eq!(sym.name().to_string(), "Max<18446744073709551615>::v");
+
+        let sym = roundtrip("_ZN3MinILxn9223372036854775808EE1vE");
+
+        assert_eq!(sym.name().to_string(), "Min<-9223372036854775808>::v");
+
+        let sym = Symbol::new(
+            Name::Nested(vec![
+                Segment::Ident("Matrix".into()),
+                Segment::TemplateArg(TemplateArg::Type(Type::Float.into())),
+                Segment::TemplateArg(TemplateArg::Literal(Type::Ulong.into(), 4)),
+                Segment::Ident("get".into()),
+            ]),
+            Some(Signature::new(None, vec![Type::Int, Type::Int], true)),
+        );
+
+        assert_eq!(sym.to_itanium(), "\u{1}_ZNK6MatrixIfLm4EE3getEii");
+    }
+
+    #[test]
+    fn substitution() {
+        let foo = class(&["ns", "Foo"]);
+        let sym = Symbol::new(
+            Name::Nested(vec![
+                Segment::Ident("ns".into()),
+                Segment::Ident("Foo".into()),
+                Segment::Ident("bar".into()),
+            ]),
+            Some(Signature::new(
+                None,
+                vec![
+                    Type::Ref {
+                        c: true,
+                        t: foo.clone().into(),
+                    },
+                    Type::Ptr {
+                        c: false,
+                        t: foo.into(),
+                    },
+                ],
+                false,
+            )),
+        );
+
+        assert_eq!(sym.to_itanium(), "\u{1}_ZN2ns3Foo3barERKS0_PS0_");
+
+        let sym = Symbol::new(
+            Name::Nested(vec![
+                Segment::Ident("ns".into()),
+                Segment::Ident("Foo".into()),
+                Segment::Ident("baz".into()),
+            ]),
+            Some(Signature::new(None, vec![class(&["ns", "Bar"])], false)),
+        );
+
+        assert_eq!(sym.to_itanium(), "\u{1}_ZN2ns3Foo3bazENS_3BarE");
+
+        let sym = roundtrip("_ZN4infoISt8functionIFvRK5EventEEE4sizeE");
+
+        assert_eq!(
+            sym.name().to_string(),
+            "info<std::function<void(const Event&)>>::size"
+        );
+    }
+
+    #[test]
+    fn template_param() {
+        let sym = Symbol::new(
+            Name::Nested(vec![
+                Segment::Ident("max".into()),
+                Segment::TemplateArg(TemplateArg::Type(Type::Int.into())),
+            ]),
+            Some(Signature::new(
+                Some(Type::Param(0)),
+                vec![Type::Param(0), Type::Param(0)],
+                false,
+            )),
+        );
+
+        assert_eq!(sym.to_itanium(), "\u{1}_Z3maxIiET_S0_S0_");
+    }
+}
@@ -1,5 +1,6 @@
 use crate::ty::Type;
 use std::borrow::Cow;
+use std::fmt::{Display, Formatter};
 use thiserror::Error;
 
 mod itanium;
@@ -7,6 +8,7 @@ mod itanium;
 /// C++ symbol.
 #[derive(Debug)]
 pub struct Symbol<'a> {
+    special: Option<Special>,
     name: Name<'a>,
     sig: Option<Signature>,
 }
@@ -27,7 +29,24 @@ impl Symbol<'static> {
 
 impl<'a> Symbol<'a> {
     pub fn new(name: Name<'a>, sig: Option<Signature>) -> Self {
-        Self { name, sig }
+        Self {
+            special: None,
+            name,
+            sig,
+        }
+    }
+
+    /// Creates a special symbol associated with class `name`.
+    pub fn new_special(special: Special, name: Name<'a>) -> Self {
+        Self {
+            special: Some(special),
+            name,
+            sig: None,
+        }
+    }
+
+    pub fn special(&self) -> Option<Special> {
+        self.special
     }
 
     pub fn name(&self) -> &Name<'a> {
@@ -35,86 +54,309 @@ impl<'a> Symbol<'a> {
     }
 
     pub fn to_itanium(&self) -> String {
-        // Build name.
         let mut name = String::from("\u{1}_Z");
-        let push_segment = |n: &mut String, s: &Segment| {
-            use std::fmt::Write;
 
-            match s {
-                Segment::Ident(v) => write!(n, "{}{}", v.len(), v).unwrap(),
-                Segment::TemplateArg(_) => todo!(),
-                Segment::Ctor => n.push_str("C1"),
-                Segment::Dtor => n.push_str("D1"),
-                Segment::New => n.push_str("nw"),
-                Segment::Delete => n.push_str("dl"),
-            }
-        };
+        self::itanium::mangle(&mut name, self);
 
-        match &self.name {
-            Name::Nested(v) => {
-                name.push('N');
+        name
+    }
+}
 
-                for s in v {
-                    push_segment(&mut name, s);
-                }
+/// Name of a C++ symbol.
+///
+/// The [`Display`] implementation of this type produces a fully qualified C++ name (e.g.
+/// `ns::Handle`), which is the same format used as a key of the metadata.
+#[derive(Debug, Clone)]
+pub enum Name<'a> {
+    Nested(Vec<Segment<'a>>),
+    Unscoped(Segment<'a>),
+}
 
-                name.push('E');
-            }
-            Name::Unscoped(s) => push_segment(&mut name, s),
+impl<'a> Name<'a> {
+    pub fn segments(&self) -> &[Segment<'a>] {
+        match self {
+            Self::Nested(v) => v.as_slice(),
+            Self::Unscoped(v) => std::slice::from_ref(v),
         }
+    }
+}
 
-        // Build signature.
-        fn push_type(n: &mut String, t: &Type) {
-            match t {
-                Type::Void => n.push('v'),
-                Type::Ulong => n.push('m'),
-                Type::Ptr { c: _, t } => push_type(n, &t),
+impl PartialEq for Name<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        // A single segment is the same name regardless how it was qualified.
+        self.segments() == other.segments()
+    }
+}
+
+impl Eq for Name<'_> {}
+
+impl Display for Name<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let segments = self.segments();
+        let mut prev = "";
+        let mut args = false;
+
+        for (i, s) in segments.iter().enumerate() {
+            // Close template arguments.
+            if args && !matches!(s, Segment::TemplateArg(_)) {
+                f.write_str(">")?;
+                args = false;
             }
-        }
 
-        if let Some(s) = &self.sig {
-            for p in &s.params {
-                push_type(&mut name, p);
+            if i != 0 && !matches!(s, Segment::TemplateArg(_)) {
+                f.write_str("::")?;
+            }
+
+            match s {
+                Segment::Ident(v) => {
+                    f.write_str(v)?;
+                    prev = v;
+                }
+                Segment::TemplateArg(v) => {
+                    f.write_str(if args { ", " } else { "<" })?;
+                    v.fmt(f)?;
+                    args = true;
+                }
+                Segment::Ctor(_) => f.write_str(prev)?,
+                Segment::Dtor(_) => write!(f, "~{prev}")?,
+                Segment::New => f.write_str("operator new")?,
+                Segment::Delete => f.write_str("operator delete")?,
+                Segment::Operator(v) => write!(f, "operator{v}")?,
             }
         }
 
-        name
+        if args {
+            f.write_str(">")?;
+        }
+
+        Ok(())
     }
 }
 
-/// Name of a C++ symbol.
-#[derive(Debug)]
-pub enum Name<'a> {
-    Nested(Vec<Segment<'a>>),
-    Unscoped(Segment<'a>),
+/// Kind of a special symbol generated by the compiler.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Special {
+    TypeInfo,
+    TypeName,
+}
+
+/// Variant of a constructor.
+///
+/// Both variants are the same unless the class has a virtual base, in which case the base object
+/// constructor does not construct the virtual bases and take a VTT as an additional parameter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CtorKind {
+    Complete,
+    Base,
+}
+
+/// Variant of a destructor.
+///
+/// The deleting destructor invokes the complete object destructor then releases the memory with
+/// `operator delete`, which only exists when the destructor is virtual. The base object destructor
+/// does not destroy the virtual bases.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DtorKind {
+    Deleting,
+    Complete,
+    Base,
 }
 
 /// Segment of a C++ name.
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Segment<'a> {
     Ident(Cow<'a, str>),
-    TemplateArg(TemplateArg<'a>),
-    Ctor,
-    Dtor,
+    TemplateArg(TemplateArg),
+    Ctor(CtorKind),
+    Dtor(DtorKind),
     New,
     Delete,
+    Operator(Operator),
+}
+
+impl Segment<'_> {
+    pub fn to_static(&self) -> Segment<'static> {
+        match self {
+            Self::Ident(v) => Segment::Ident(Cow::Owned(v.as_ref().to_owned())),
+            Self::TemplateArg(v) => Segment::TemplateArg(v.clone()),
+            Self::Ctor(v) => Segment::Ctor(*v),
+            Self::Dtor(v) => Segment::Dtor(*v),
+            Self::New => Segment::New,
+            Self::Delete => Segment::Delete,
+            Self::Operator(v) => Segment::Operator(*v),
+        }
+    }
+}
+
+/// Overloadable C++ operator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operator {
+    Pos,
+    Neg,
+    Deref,
+    AddrOf,
+    Not,
+    BitNot,
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+    BitAnd,
+    BitOr,
+    BitXor,
+    Shl,
+    Shr,
+    Assign,
+    AddAssign,
+    SubAssign,
+    MulAssign,
+    DivAssign,
+    RemAssign,
+    BitAndAssign,
+    BitOrAssign,
+    BitXorAssign,
+    ShlAssign,
+    ShrAssign,
+    Eq,
+    Ne,
+    Lt,
+    Gt,
+    Le,
+    Ge,
+    Index,
+    Call,
+}
+
+impl Operator {
+    /// Returns the unary version of this operator or [`None`] if this operator cannot be unary.
+    pub fn to_unary(self) -> Option<Self> {
+        let op = match self {
+            Self::Pos | Self::Neg | Self::Deref | Self::AddrOf | Self::Not | Self::BitNot => self,
+            Self::Add => Self::Pos,
+            Self::Sub => Self::Neg,
+            Self::Mul => Self::Deref,
+            Self::BitAnd => Self::AddrOf,
+            _ => return None,
+        };
+
+        Some(op)
+    }
+
+    /// Returns the code of this operator in Itanium C++ ABI.
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::Pos => "ps",
+            Self::Neg => "ng",
+            Self::Deref => "de",
+            Self::AddrOf => "ad",
+            Self::Not => "nt",
+            Self::BitNot => "co",
+            Self::Add => "pl",
+            Self::Sub => "mi",
+            Self::Mul => "ml",
+            Self::Div => "dv",
+            Self::Rem => "rm",
+            Self::BitAnd => "an",
+            Self::BitOr => "or",
+            Self::BitXor => "eo",
+            Self::Shl => "ls",
+            Self::Shr => "rs",
+            Self::Assign => "aS",
+            Self::AddAssign => "pL",
+            Self::SubAssign => "mI",
+            Self::MulAssign => "mL",
+            Self::DivAssign => "dV",
+            Self::RemAssign => "rM",
+            Self::BitAndAssign => "aN",
+            Self::BitOrAssign => "oR",
+            Self::BitXorAssign => "eO",
+            Self::ShlAssign => "lS",
+            Self::ShrAssign => "rS",
+            Self::Eq => "eq",
+            Self::Ne => "ne",
+            Self::Lt => "lt",
+            Self::Gt => "gt",
+            Self::Le => "le",
+            Self::Ge => "ge",
+            Self::Index => "ix",
+            Self::Call => "cl",
+        }
+    }
+}
+
+impl Display for Operator {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let v = match self {
+            Self::Pos | Self::Add => "+",
+            Self::Neg | Self::Sub => "-",
+            Self::Deref | Self::Mul => "*",
+            Self::AddrOf | Self::BitAnd => "&",
+            Self::Not => "!",
+            Self::BitNot => "~",
+            Self::Div => "/",
+            Self::Rem => "%",
+            Self::BitOr => "|",
+            Self::BitXor => "^",
+            Self::Shl => "<<",
+            Self::Shr => ">>",
+            Self::Assign => "=",
+            Self::AddAssign => "+=",
+            Self::SubAssign => "-=",
+            Self::MulAssign => "*=",
+            Self::DivAssign => "/=",
+            Self::RemAssign => "%=",
+            Self::BitAndAssign => "&=",
+            Self::BitOrAssign => "|=",
+            Self::BitXorAssign => "^=",
+            Self::ShlAssign => "<<=",
+            Self::ShrAssign => ">>=",
+            Self::Eq => "==",
+            Self::Ne => "!=",
+            Self::Lt => "<",
+            Self::Gt => ">",
+            Self::Le => "<=",
+            Self::Ge => ">=",
+            Self::Index => "[]",
+            Self::Call => "()",
+        };
+
+        f.write_str(v)
+    }
 }
 
 /// Argument of a template instantiation.
-#[derive(Debug, PartialEq, Eq)]
-pub enum TemplateArg<'a> {
-    Ident(Cow<'a, str>),
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TemplateArg {
+    Type(Box<Type>),
+    Literal(Box<Type>, i128), // Wide enough for both i64 and u64.
+}
+
+impl Display for TemplateArg {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Type(v) => v.fmt(f),
+            Self::Literal(t, v) if **t == Type::Bool => {
+                f.write_str(if *v == 0 { "false" } else { "true" })
+            }
+            Self::Literal(_, v) => v.fmt(f),
+        }
+    }
 }
 
 /// Signature of C++ function.
+///
+/// `ret` is only encoded for a function template. `c` indicates a `const` member function.
 #[derive(Debug)]
 pub struct Signature {
+    ret: Option<Type>,
     params: Vec<Type>,
+    c: bool,
 }
 
 impl Signature {
-    pub fn new(params: Vec<Type>) -> Self {
-        Self { params }
+    pub fn new(ret: Option<Type>, params: Vec<Type>, c: bool) -> Self {
+        Self { ret, params, c }
     }
 }
 
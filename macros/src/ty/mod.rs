@@ -1,15 +1,405 @@
+use crate::symbol::{Name, Segment, TemplateArg};
+use std::fmt::{Display, Formatter};
+use syn::ext::IdentExt;
 use syn::parse::{Parse, ParseStream};
+use syn::token::Paren;
+use syn::{parenthesized, Error, Ident, LitInt, Token};
 
 /// C++ type.
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Type {
     Void,
+    Bool,
+    Char,
+    Schar,
+    Uchar,
+    Short,
+    Ushort,
+    Int,
+    Uint,
+    Long,
     Ulong,
+    Longlong,
+    Ulonglong,
+    Float,
+    Double,
     Ptr { c: bool, t: Box<Self> },
+    Ref { c: bool, t: Box<Self> },
+    RvalueRef { c: bool, t: Box<Self> },
+    Func { ret: Box<Self>, params: Vec<Self> },
+    Class(Name<'static>),
+    Param(usize), // Template parameter at the specified index.
+}
+
+impl Type {
+    /// Parses the type and return its top-level constness.
+    pub fn parse_with_const(input: ParseStream) -> syn::Result<(Self, bool)> {
+        let (ty, c, name) = Self::parse_declaration(input)?;
+
+        match name {
+            Some(v) => Err(Error::new_spanned(v, "unexpected declarator name")),
+            None => Ok((ty, c)),
+        }
+    }
+
+    /// Parses the type of a parameter and return the name if it is inside the declarator (e.g.
+    /// `void (*handler)(int)`).
+    pub fn parse_param(input: ParseStream) -> syn::Result<(Self, Option<Ident>)> {
+        Self::parse_declaration(input).map(|v| (v.0, v.2))
+    }
+
+    fn parse_declaration(input: ParseStream) -> syn::Result<(Self, bool, Option<Ident>)> {
+        // Parse base type.
+        let mut c = false;
+        let mut words = Vec::<Ident>::new();
+
+        loop {
+            if input.peek(Token![const]) {
+                input.parse::<Token![const]>()?;
+                c = true;
+            } else if input.peek(Ident::peek_any) {
+                let fork = input.fork();
+                let w = fork.call(Ident::parse_any)?;
+
+                if !BUILTINS.iter().any(|&v| w == v) {
+                    break;
+                }
+
+                input.call(Ident::parse_any)?;
+                words.push(w);
+            } else {
+                break;
+            }
+        }
+
+        let mut ty = if words.is_empty() {
+            let mut segments = Vec::new();
+
+            if input.peek(Token![::]) {
+                return Err(input.error("global qualified name is not supported"));
+            }
+
+            loop {
+                segments.push(Segment::Ident(
+                    input.call(Ident::parse_any)?.to_string().into(),
+                ));
+
+                if input.peek(Token![<]) {
+                    segments.extend(parse_template_args(input)?);
+                }
+
+                if !input.peek(Token![::]) {
+                    break;
+                }
+
+                input.parse::<Token![::]>()?;
+            }
+
+            // Allow east const.
+            if input.peek(Token![const]) {
+                input.parse::<Token![const]>()?;
+                c = true;
+            }
+
+            Self::Class(Name::Nested(segments))
+        } else {
+            Self::from_words(input, &words)?
+        };
+
+        // Parse declarator.
+        loop {
+            if input.peek(Token![*]) {
+                input.parse::<Token![*]>()?;
+                ty = Self::Ptr { c, t: ty.into() };
+                c = false;
+            } else if input.peek(Token![&&]) {
+                input.parse::<Token![&&]>()?;
+                ty = Self::RvalueRef { c, t: ty.into() };
+                c = false;
+            } else if input.peek(Token![&]) {
+                input.parse::<Token![&]>()?;
+                ty = Self::Ref { c, t: ty.into() };
+                c = false;
+            } else if input.peek(Token![const]) {
+                input.parse::<Token![const]>()?;
+                c = true;
+            } else {
+                break;
+            }
+        }
+
+        // Parse function type or pointer to function.
+        let mut name = None;
+
+        if input.peek(Paren) {
+            let ptr = {
+                let fork = input.fork();
+                let inner;
+
+                parenthesized!(inner in fork);
+                inner.peek(Token![*])
+            };
+
+            if ptr {
+                let inner;
+
+                parenthesized!(inner in input);
+                inner.parse::<Token![*]>()?;
+
+                // The top-level constness is not part of the type.
+                inner.parse::<Option<Token![const]>>()?;
+
+                if !inner.is_empty() {
+                    name = Some(inner.call(Ident::parse_any)?);
+                }
+            }
+
+            let args;
+
+            parenthesized!(args in input);
+
+            let mut params = Vec::new();
+
+            while !args.is_empty() {
+                // The top-level constness of a parameter is not part of the function type.
+                params.push(Self::parse_param(&args)?.0);
+
+                if args.peek(Ident::peek_any) {
+                    args.call(Ident::parse_any)?;
+                }
+
+                if args.is_empty() {
+                    break;
+                }
+
+                args.parse::<Token![,]>()?;
+            }
+
+            // A parameter list of (void) means no parameters.
+            if params == [Self::Void] {
+                params.clear();
+            }
+
+            ty = Self::Func {
+                ret: ty.into(),
+                params,
+            };
+
+            if ptr {
+                ty = Self::Ptr {
+                    c: false,
+                    t: ty.into(),
+                };
+            }
+
+            c = false;
+        }
+
+        Ok((ty, c, name))
+    }
+
+    fn from_words(input: ParseStream, words: &[Ident]) -> syn::Result<Self> {
+        let mut signed = None;
+        let mut short = false;
+        let mut long = 0;
+        let mut base = None;
+
+        for w in words {
+            let w = w.to_string();
+
+            match w.as_str() {
+                "signed" | "unsigned" if signed.is_none() => signed = Some(w == "signed"),
+                "short" if !short && long == 0 => short = true,
+                "long" if !short && long < 2 => long += 1,
+                "signed" | "unsigned" | "short" | "long" => {
+                    return Err(input.error(format_args!("unexpected '{w}'")))
+                }
+                _ if base.is_none() => base = Some(w),
+                _ => return Err(input.error(format_args!("unexpected '{w}'"))),
+            }
+        }
+
+        let ty = match (base.as_deref(), signed, short, long) {
+            (Some("void"), None, false, 0) => Self::Void,
+            (Some("bool"), None, false, 0) => Self::Bool,
+            (Some("char"), None, false, 0) => Self::Char,
+            (Some("char"), Some(true), false, 0) => Self::Schar,
+            (Some("char"), Some(false), false, 0) => Self::Uchar,
+            (Some("int") | None, Some(true) | None, true, 0) => Self::Short,
+            (Some("int") | None, Some(false), true, 0) => Self::Ushort,
+            (Some("int") | None, Some(true) | None, false, 0) => Self::Int,
+            (Some("int") | None, Some(false), false, 0) => Self::Uint,
+            (Some("int") | None, Some(true) | None, false, 1) => Self::Long,
+            (Some("int") | None, Some(false), false, 1) => Self::Ulong,
+            (Some("int") | None, Some(true) | None, false, 2) => Self::Longlong,
+            (Some("int") | None, Some(false), false, 2) => Self::Ulonglong,
+            (Some("float"), None, false, 0) => Self::Float,
+            (Some("double"), None, false, 0) => Self::Double,
+            _ => return Err(input.error("unsupported type")),
+        };
+
+        Ok(ty)
+    }
 }
 
 impl Parse for Type {
     fn parse(input: ParseStream) -> syn::Result<Self> {
-        todo!()
+        Self::parse_with_const(input).map(|v| v.0)
+    }
+}
+
+impl Display for Type {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Void => f.write_str("void"),
+            Self::Bool => f.write_str("bool"),
+            Self::Char => f.write_str("char"),
+            Self::Schar => f.write_str("signed char"),
+            Self::Uchar => f.write_str("unsigned char"),
+            Self::Short => f.write_str("short"),
+            Self::Ushort => f.write_str("unsigned short"),
+            Self::Int => f.write_str("int"),
+            Self::Uint => f.write_str("unsigned int"),
+            Self::Long => f.write_str("long"),
+            Self::Ulong => f.write_str("unsigned long"),
+            Self::Longlong => f.write_str("long long"),
+            Self::Ulonglong => f.write_str("unsigned long long"),
+            Self::Float => f.write_str("float"),
+            Self::Double => f.write_str("double"),
+            Self::Ptr { c, t } => match t.as_ref() {
+                Self::Func { ret, params } => {
+                    write!(f, "{ret} (*)(")?;
+                    fmt_params(f, params)?;
+                    f.write_str(")")
+                }
+                t => fmt_declarator(f, *c, t, "*"),
+            },
+            Self::Ref { c, t } => fmt_declarator(f, *c, t, "&"),
+            Self::RvalueRef { c, t } => fmt_declarator(f, *c, t, "&&"),
+            Self::Func { ret, params } => {
+                write!(f, "{ret}(")?;
+                fmt_params(f, params)?;
+                f.write_str(")")
+            }
+            Self::Class(v) => v.fmt(f),
+            Self::Param(i) => write!(f, "T{i}"),
+        }
+    }
+}
+
+/// Parses template arguments of a template-id.
+pub fn parse_template_args(input: ParseStream) -> syn::Result<Vec<Segment<'static>>> {
+    let mut args = Vec::new();
+
+    input.parse::<Token![<]>()?;
+
+    while !input.peek(Token![>]) {
+        let arg = if input.peek(LitInt) || input.peek(Token![-]) {
+            let neg = input.parse::<Option<Token![-]>>()?.is_some();
+            let lit = input.parse::<LitInt>()?;
+            let val = i128::from(lit.base10_parse::<u64>()?);
+
+            if neg && val > i128::from(i64::MAX) + 1 {
+                return Err(Error::new_spanned(lit, "integer literal is too small"));
+            }
+
+            // The type of a literal is determined by its suffix. The actual type will be replaced
+            // with the one from the metadata if the class is known.
+            let ty = match lit.suffix().to_ascii_lowercase().as_str() {
+                "" => Type::Int,
+                "u" => Type::Uint,
+                "l" => Type::Long,
+                "ul" | "lu" => Type::Ulong,
+                "ll" => Type::Longlong,
+                "ull" | "llu" => Type::Ulonglong,
+                _ => return Err(Error::new_spanned(lit, "unsupported integer suffix")),
+            };
+
+            TemplateArg::Literal(ty.into(), if neg { -val } else { val })
+        } else if input.peek(syn::LitBool) {
+            let val = input.parse::<syn::LitBool>()?.value;
+
+            TemplateArg::Literal(Type::Bool.into(), val.into())
+        } else {
+            TemplateArg::Type(input.parse::<Type>()?.into())
+        };
+
+        args.push(Segment::TemplateArg(arg));
+
+        if !input.peek(Token![,]) {
+            break;
+        }
+
+        input.parse::<Token![,]>()?;
+    }
+
+    input.parse::<Token![>]>()?;
+
+    Ok(args)
+}
+
+fn fmt_declarator(f: &mut Formatter, c: bool, t: &Type, d: &str) -> std::fmt::Result {
+    match (c, t) {
+        (true, Type::Ptr { .. } | Type::Ref { .. } | Type::RvalueRef { .. }) => {
+            write!(f, "{t} const{d}")
+        }
+        (true, t) => write!(f, "const {t}{d}"),
+        (false, t) => write!(f, "{t}{d}"),
+    }
+}
+
+fn fmt_params(f: &mut Formatter, params: &[Type]) -> std::fmt::Result {
+    for (i, p) in params.iter().enumerate() {
+        if i != 0 {
+            f.write_str(", ")?;
+        }
+
+        p.fmt(f)?;
+    }
+
+    Ok(())
+}
+
+const BUILTINS: [&str; 10] = [
+    "void", "bool", "char", "short", "int", "long", "float", "double", "signed", "unsigned",
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn literal_suffix() {
+        let ty = syn::parse_str::<Type>("Array<float, 4ul>").unwrap();
+        let arg = match &ty {
+            Type::Class(n) => n.segments().last().unwrap(),
+            _ => panic!("unexpected type"),
+        };
+
+        assert_eq!(
+            *arg,
+            Segment::TemplateArg(TemplateArg::Literal(Type::Ulong.into(), 4))
+        );
+    }
+
+    #[test]
+    fn literal_range() {
+        let arg = |s: &str| match syn::parse_str::<Type>(s).unwrap() {
+            Type::Class(n) => n.segments().last().unwrap().clone(),
+            _ => panic!("unexpected type"),
+        };
+
+        assert_eq!(
+            arg("Foo<18446744073709551615ull>"),
+            Segment::TemplateArg(TemplateArg::Literal(
+                Type::Ulonglong.into(),
+                u64::MAX.into()
+            ))
+        );
+        assert_eq!(
+            arg("Foo<-9223372036854775808ll>"),
+            Segment::TemplateArg(TemplateArg::Literal(Type::Longlong.into(), i64::MIN.into()))
+        );
+        assert!(syn::parse_str::<Type>("Foo<-9223372036854775809ll>").is_err());
     }
 }
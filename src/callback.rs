@@ -0,0 +1,55 @@
+use crate::abort_on_panic;
+use std::ffi::c_void;
+
+/// Closure that can be passed to C++ as a pointer to function that take a context pointer as the
+/// last parameter (e.g. `void (*)(int, void *)`).
+///
+/// `A` is a tuple of the parameters except the context.
+pub trait Callback<A> {
+    /// Type of the pointer to function.
+    type Fn: Copy;
+
+    /// Returns a trampoline that invoke `self` and a context pointer to pass to it. A panic inside
+    /// the closure is caught by the trampoline and will abort the process.
+    ///
+    /// # Safety
+    /// The context is a pointer to `self` without a lifetime so `self` must outlive every call to
+    /// the trampoline and must not be moved or accessed while C++ holding the context.
+    unsafe fn as_callback(&mut self) -> (Self::Fn, *mut c_void);
+}
+
+macro_rules! callback {
+    ($($a:ident),*) => {
+        impl<F, R, $($a),*> Callback<($($a,)*)> for F
+        where
+            F: FnMut($($a),*) -> R,
+        {
+            type Fn = unsafe extern "C" fn($($a,)* *mut c_void) -> R;
+
+            unsafe fn as_callback(&mut self) -> (Self::Fn, *mut c_void) {
+                #[allow(non_snake_case)]
+                unsafe extern "C" fn trampoline<F, R, $($a),*>(
+                    $($a: $a,)*
+                    context: *mut c_void,
+                ) -> R
+                where
+                    F: FnMut($($a),*) -> R,
+                {
+                    let f = &mut *context.cast::<F>();
+
+                    abort_on_panic(move || f($($a),*))
+                }
+
+                (trampoline::<F, R, $($a),*>, (self as *mut F).cast())
+            }
+        }
+    };
+}
+
+callback!();
+callback!(A1);
+callback!(A1, A2);
+callback!(A1, A2, A3);
+callback!(A1, A2, A3, A4);
+callback!(A1, A2, A3, A4, A5);
+callback!(A1, A2, A3, A4, A5, A6);
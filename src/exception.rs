@@ -0,0 +1,87 @@
+use std::error::Error;
+use std::ffi::{c_char, CStr, CString};
+use std::fmt::{Display, Formatter};
+use std::mem::MaybeUninit;
+
+/// Invokes a thunk exported by `CPPBIND_THROWING` and returns the exception it caught.
+///
+/// # Safety
+/// `f` must pass its arguments to the thunk with the return type `R`.
+pub unsafe fn try_invoke<R>(f: impl FnOnce(*mut Catcher, *mut R) -> bool) -> Result<R, Exception> {
+    let mut catcher = Catcher {
+        set: Catcher::set,
+        exception: None,
+    };
+    let mut ret = MaybeUninit::<R>::uninit();
+
+    if f(&mut catcher, ret.as_mut_ptr()) {
+        Ok(ret.assume_init())
+    } else {
+        Err(catcher.exception.unwrap_or(Exception {
+            what: None,
+            ty: None,
+        }))
+    }
+}
+
+/// Exception that was thrown from C++.
+#[derive(Debug, Clone)]
+pub struct Exception {
+    what: Option<CString>,
+    ty: Option<CString>,
+}
+
+impl Exception {
+    /// Returns the result of `std::exception::what()` or [`None`] if the exception is not derived
+    /// from `std::exception`.
+    pub fn what(&self) -> Option<&CStr> {
+        self.what.as_deref()
+    }
+
+    /// Returns the mangled name of the exception type (e.g. `St13runtime_error`).
+    pub fn type_name(&self) -> Option<&CStr> {
+        self.ty.as_deref()
+    }
+}
+
+impl Error for Exception {}
+
+impl Display for Exception {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("C++ exception")?;
+
+        if let Some(v) = &self.ty {
+            write!(f, " {}", v.to_string_lossy())?;
+        }
+
+        if let Some(v) = &self.what {
+            write!(f, ": {}", v.to_string_lossy())?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Implementation of `cppbind::detail::error`.
+#[repr(C)]
+pub struct Catcher {
+    set: unsafe extern "C" fn(*mut Self, *const c_char, *const c_char),
+    exception: Option<Exception>,
+}
+
+impl Catcher {
+    unsafe extern "C" fn set(this: *mut Self, what: *const c_char, ty: *const c_char) {
+        let what = (!what.is_null()).then(|| CStr::from_ptr(what).to_owned());
+        let ty = (!ty.is_null()).then(|| {
+            // GCC prefix the name with '*' if it need to be compared by the address.
+            let ty = CStr::from_ptr(ty);
+
+            match ty.to_bytes().strip_prefix(b"*") {
+                Some(v) => CString::new(v).unwrap(),
+                None => ty.to_owned(),
+            }
+        });
+
+        (*this).exception = Some(Exception { what, ty });
+    }
+}
@@ -1,6 +1,39 @@
 unsafe extern "C-unwind" {
+    #[link_name = "\u{1}_ZSt7nothrow"]
+    pub static NOTHROW: u8;
     #[link_name = "\u{1}_Znwm"]
     pub fn new(len: usize) -> *mut ();
+    #[link_name = "\u{1}_ZnwmSt11align_val_t"]
+    pub fn new_aligned(len: usize, align: usize) -> *mut ();
+    #[link_name = "\u{1}_ZnwmRKSt9nothrow_t"]
+    pub fn new_nothrow(len: usize, tag: *const u8) -> *mut ();
+    #[link_name = "\u{1}_ZnwmSt11align_val_tRKSt9nothrow_t"]
+    pub fn new_aligned_nothrow(len: usize, align: usize, tag: *const u8) -> *mut ();
+    #[link_name = "\u{1}_Znam"]
+    pub fn new_array(len: usize) -> *mut ();
+    #[link_name = "\u{1}_ZnamSt11align_val_t"]
+    pub fn new_array_aligned(len: usize, align: usize) -> *mut ();
+    #[link_name = "\u{1}_ZdlPv"]
+    pub fn delete_unsized(ptr: *mut ());
     #[link_name = "\u{1}_ZdlPvm"]
     pub fn delete(ptr: *mut (), len: usize);
+    #[link_name = "\u{1}_ZdlPvSt11align_val_t"]
+    pub fn delete_unsized_aligned(ptr: *mut (), align: usize);
+    #[link_name = "\u{1}_ZdlPvmSt11align_val_t"]
+    pub fn delete_aligned(ptr: *mut (), len: usize, align: usize);
+    #[link_name = "\u{1}_ZdaPv"]
+    pub fn delete_array_unsized(ptr: *mut ());
+    #[link_name = "\u{1}_ZdaPvm"]
+    pub fn delete_array(ptr: *mut (), len: usize);
+    #[link_name = "\u{1}_ZdaPvSt11align_val_t"]
+    pub fn delete_array_unsized_aligned(ptr: *mut (), align: usize);
+    #[link_name = "\u{1}_ZdaPvmSt11align_val_t"]
+    pub fn delete_array_aligned(ptr: *mut (), len: usize, align: usize);
+    #[link_name = "__dynamic_cast"]
+    pub fn dynamic_cast(
+        src: *const (),
+        src_type: *const (),
+        dst_type: *const (),
+        hint: isize,
+    ) -> *mut ();
 }
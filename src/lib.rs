@@ -1,7 +1,20 @@
+pub use self::callback::*;
+pub use self::exception::*;
 pub use self::ffi::*;
+pub use self::panic::*;
+pub use self::vtable::*;
 pub use cppbind_macros::*;
 
+use std::error::Error;
+use std::ffi::CStr;
+use std::fmt::{Display, Formatter};
+use std::ptr::NonNull;
+
+mod callback;
+mod exception;
 mod ffi;
+mod panic;
+mod vtable;
 
 /// Memory of a C++ class that live on a heap.
 pub struct Heap<T: HeapAlloc>(*mut T);
@@ -10,30 +23,278 @@ impl<T: HeapAlloc> Heap<T> {
     pub fn new() -> Self {
         Self(T::alloc().cast())
     }
+
+    /// # Safety
+    /// `ptr` must be allocated with the same `operator new` as [`HeapAlloc::alloc()`] since it
+    /// will be released with [`HeapAlloc::dealloc()`] (e.g. `::operator delete(void*, size_t)` for a
+    /// class that does not have its own allocation functions).
+    pub unsafe fn from_raw(ptr: *mut T) -> Self {
+        Self(ptr)
+    }
+
+    /// Releases the memory without deallocating it. The caller is responsible for releasing the
+    /// returned pointer with the `operator delete` matched with [`HeapAlloc::alloc()`].
+    pub fn into_raw(self) -> *mut T {
+        let ptr = self.0;
+
+        std::mem::forget(self);
+
+        ptr
+    }
+}
+
+impl<T: TryHeapAlloc> Heap<T> {
+    pub fn try_new() -> Result<Self, AllocError> {
+        let ptr = T::try_alloc();
+
+        if ptr.is_null() {
+            Err(AllocError)
+        } else {
+            Ok(Self(ptr.cast()))
+        }
+    }
+}
+
+impl<T: HeapAlloc> Default for Heap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl<T: HeapAlloc> Drop for Heap<T> {
     fn drop(&mut self) {
-        unsafe { T::dealloc(self.0.cast()) };
+        // The memory was released by the deleting destructor if the object was destroyed.
+        if !self.0.is_null() {
+            unsafe { T::dealloc(self.0.cast()) };
+        }
     }
 }
 
 impl<T: HeapAlloc> Memory for Heap<T> {
     type Class = T::Class;
 
+    fn as_ptr(&self) -> *const () {
+        self.0.cast()
+    }
+
     fn as_mut_ptr(&mut self) -> *mut () {
         self.0.cast()
     }
+
+    unsafe fn destroy(&mut self) {
+        T::delete(self.0.cast());
+        self.0 = std::ptr::null_mut();
+    }
 }
 
 unsafe impl<T: HeapAlloc + Send> Send for Heap<T> {}
 unsafe impl<T: HeapAlloc + Sync> Sync for Heap<T> {}
 
+/// Array of C++ objects that live on a heap.
+///
+/// The memory layout is the same as C++ `new T[len]` so it can be passed to and from C++.
+pub struct HeapArray<T: ArrayAlloc> {
+    ptr: *mut T,
+    len: usize,
+}
+
+impl<T: ArrayAlloc> HeapArray<T> {
+    /// Allocates an array with `operator new[]` and invoke `init` to construct each element.
+    ///
+    /// # Safety
+    /// `init` must construct an object on the specified memory.
+    pub unsafe fn new(len: usize, mut init: impl FnMut(*mut T)) -> Self {
+        // Allocate.
+        let size = Self::size(len);
+        let raw = T::alloc(size).cast::<u8>();
+        let ptr = raw.add(T::COOKIE).cast::<T>();
+
+        if T::COOKIE != 0 {
+            ptr.cast::<usize>().sub(1).write(len);
+        }
+
+        // Construct elements. The constructed elements will be destroyed if the constructor unwind.
+        struct Guard<T: ArrayAlloc> {
+            ptr: *mut T,
+            constructed: usize,
+            len: usize,
+        }
+
+        impl<T: ArrayAlloc> Drop for Guard<T> {
+            fn drop(&mut self) {
+                unsafe { HeapArray::release(self.ptr, self.constructed, self.len) };
+            }
+        }
+
+        let mut guard = Guard {
+            ptr,
+            constructed: 0,
+            len,
+        };
+
+        for i in 0..len {
+            init(ptr.add(i));
+            guard.constructed = i + 1;
+        }
+
+        std::mem::forget(guard);
+
+        Self { ptr, len }
+    }
+
+    /// Adopts an array that was created by C++ `new T[len]`. The number of elements is read from
+    /// the array cookie.
+    ///
+    /// # Panics
+    /// If `T` has a trivial destructor, which has no cookie. Use [`HeapArray::from_raw_parts()`]
+    /// instead.
+    ///
+    /// # Safety
+    /// `ptr` must be a pointer returned from C++ `new T[len]` with the same `operator new[]` as
+    /// [`ArrayAlloc::alloc()`].
+    pub unsafe fn from_raw(ptr: *mut T) -> Self {
+        assert_ne!(T::COOKIE, 0);
+
+        Self {
+            ptr,
+            len: ptr.cast::<usize>().sub(1).read(),
+        }
+    }
+
+    /// # Safety
+    /// `ptr` must be a pointer returned from C++ `new T[len]` with the same `operator new[]` as
+    /// [`ArrayAlloc::alloc()`].
+    pub unsafe fn from_raw_parts(ptr: *mut T, len: usize) -> Self {
+        Self { ptr, len }
+    }
+
+    /// Releases the array without destroying it. The caller is responsible for destroying it with
+    /// C++ `delete[]`.
+    pub fn into_raw(self) -> *mut T {
+        let ptr = self.ptr;
+
+        std::mem::forget(self);
+
+        ptr
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn get(&self, i: usize) -> Option<T::Ref<'_>> {
+        (i < self.len).then(|| unsafe { T::to_ref(self.ptr.add(i)) })
+    }
+
+    pub fn get_mut(&mut self, i: usize) -> Option<T::Mut<'_>> {
+        (i < self.len).then(|| unsafe { T::to_mut(self.ptr.add(i)) })
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = T::Ref<'_>> {
+        (0..self.len).map(|i| unsafe { T::to_ref(self.ptr.add(i)) })
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = T::Mut<'_>> {
+        let ptr = self.ptr;
+
+        (0..self.len).map(move |i| unsafe { T::to_mut(ptr.add(i)) })
+    }
+
+    unsafe fn release(ptr: *mut T, constructed: usize, len: usize) {
+        // Destroy elements in reverse order the same as C++.
+        for i in (0..constructed).rev() {
+            T::destroy(ptr.add(i));
+        }
+
+        T::dealloc(ptr.cast::<u8>().sub(T::COOKIE).cast(), Self::size(len));
+    }
+
+    fn size(len: usize) -> usize {
+        len.checked_mul(size_of::<T>())
+            .and_then(|v| v.checked_add(T::COOKIE))
+            .expect("array too large")
+    }
+}
+
+impl<T: ArrayAlloc> Drop for HeapArray<T> {
+    fn drop(&mut self) {
+        unsafe { Self::release(self.ptr, self.len, self.len) };
+    }
+}
+
+unsafe impl<T: ArrayAlloc + Send> Send for HeapArray<T> {}
+unsafe impl<T: ArrayAlloc + Sync> Sync for HeapArray<T> {}
+
+/// Memory of a C++ class that live inside its owner (e.g. a field of Rust struct).
+///
+/// The owner must be pinned before the object is constructed unless the class is relocatable.
+pub struct Inline<T: InlineMemory>(T);
+
+impl<T: InlineMemory> Inline<T> {
+    pub const fn new(mem: T) -> Self {
+        Self(mem)
+    }
+}
+
+impl<T: InlineMemory> Memory for Inline<T> {
+    type Class = T::Class;
+
+    fn as_ptr(&self) -> *const () {
+        (&self.0 as *const T).cast()
+    }
+
+    fn as_mut_ptr(&mut self) -> *mut () {
+        (&mut self.0 as *mut T).cast()
+    }
+
+    unsafe fn destroy(&mut self) {
+        T::destroy(&mut self.0);
+    }
+}
+
+/// Memory of a C++ class that is owned by someone else.
+pub struct Borrowed<T: BorrowedMemory>(NonNull<T>);
+
+impl<T: BorrowedMemory> Borrowed<T> {
+    /// # Safety
+    /// `ptr` must point to a constructed object that outlive the returned memory.
+    pub unsafe fn new(ptr: *mut T) -> Self {
+        Self(NonNull::new_unchecked(ptr))
+    }
+}
+
+impl<T: BorrowedMemory> Memory for Borrowed<T> {
+    type Class = T::Class;
+
+    fn as_ptr(&self) -> *const () {
+        self.0.as_ptr().cast()
+    }
+
+    fn as_mut_ptr(&mut self) -> *mut () {
+        self.0.as_ptr().cast()
+    }
+
+    unsafe fn destroy(&mut self) {
+        // The object is owned by someone else so it is never destroyed from here.
+    }
+}
+
 /// Memory of a C++ class.
 pub trait Memory {
     type Class;
 
+    fn as_ptr(&self) -> *const ();
     fn as_mut_ptr(&mut self) -> *mut ();
+
+    /// Destroys the object in this memory.
+    ///
+    /// # Safety
+    /// The object must be constructed and must not be used after this.
+    unsafe fn destroy(&mut self);
 }
 
 /// Provides methods to allocate/deallocate a memory for a class.
@@ -41,5 +302,105 @@ pub trait HeapAlloc {
     type Class;
 
     fn alloc() -> *mut ();
+
+    /// # Safety
+    /// `this` must be a pointer returned from [`HeapAlloc::alloc()`].
     unsafe fn dealloc(this: *mut ());
+
+    /// Destroys the object then releases its memory the same as C++ `delete` expression, which
+    /// use the deleting destructor if the class has a virtual destructor.
+    ///
+    /// # Safety
+    /// `this` must point to a constructed object that was allocated with `operator new` matched
+    /// with its dynamic type.
+    unsafe fn delete(this: *mut ());
+}
+
+/// Memory of a C++ class that can be owned by [`Inline`].
+pub trait InlineMemory {
+    type Class;
+
+    /// Invokes the complete object destructor.
+    ///
+    /// # Safety
+    /// `this` must point to a constructed object.
+    unsafe fn destroy(this: *mut Self);
+}
+
+/// Memory of a C++ class that can be referenced by [`Borrowed`].
+pub trait BorrowedMemory {
+    type Class;
+}
+
+/// Provides a method to allocate a memory for a class without throwing C++ exception.
+pub trait TryHeapAlloc: HeapAlloc {
+    /// Returns null if allocation fails.
+    fn try_alloc() -> *mut ();
+}
+
+/// Represents an error when C++ `operator new` fails to allocate a memory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AllocError;
+
+impl Error for AllocError {}
+
+impl Display for AllocError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("memory allocation failed")
+    }
+}
+
+/// Provides RTTI of a polymorphic class.
+pub trait Polymorphic: Sized {
+    type Ref<'a>
+    where
+        Self: 'a;
+    type Mut<'a>
+    where
+        Self: 'a;
+
+    /// Returns a pointer to `std::type_info` of the class.
+    fn type_info() -> *const ();
+
+    /// Returns the mangled name of the class.
+    fn type_name() -> &'static CStr;
+
+    /// # Safety
+    /// `this` must point to a constructed object.
+    unsafe fn to_ref<'a>(this: *const Self) -> Self::Ref<'a>;
+
+    /// # Safety
+    /// `this` must point to a constructed object.
+    unsafe fn to_mut<'a>(this: *mut Self) -> Self::Mut<'a>;
+}
+
+/// Provides methods to allocate/deallocate an array of a class.
+pub trait ArrayAlloc: Sized {
+    type Ref<'a>
+    where
+        Self: 'a;
+    type Mut<'a>
+    where
+        Self: 'a;
+
+    /// Size of the array cookie or zero if the array does not have it.
+    const COOKIE: usize;
+
+    fn alloc(len: usize) -> *mut ();
+
+    /// # Safety
+    /// `ptr` must be a pointer returned from [`ArrayAlloc::alloc()`] with the same `len`.
+    unsafe fn dealloc(ptr: *mut (), len: usize);
+
+    /// # Safety
+    /// `this` must point to a constructed object.
+    unsafe fn destroy(this: *mut Self);
+
+    /// # Safety
+    /// `this` must point to a constructed object.
+    unsafe fn to_ref<'a>(this: *const Self) -> Self::Ref<'a>;
+
+    /// # Safety
+    /// `this` must point to a constructed object.
+    unsafe fn to_mut<'a>(this: *mut Self) -> Self::Mut<'a>;
 }
@@ -0,0 +1,9 @@
+use std::panic::AssertUnwindSafe;
+
+/// Invokes `f` and aborts the process if it panics so the panic never unwinds into C++ frames.
+pub fn abort_on_panic<R>(f: impl FnOnce() -> R) -> R {
+    match std::panic::catch_unwind(AssertUnwindSafe(f)) {
+        Ok(v) => v,
+        Err(_) => std::process::abort(),
+    }
+}
@@ -0,0 +1,30 @@
+use std::ffi::{c_char, CStr};
+
+/// Returns a function in the vtable slot at `offset` bytes of the polymorphic object `this`.
+///
+/// # Safety
+/// `this` must point to a constructed polymorphic object and `offset` must be a valid slot offset.
+pub unsafe fn virtual_fn(this: *const (), offset: usize) -> *const () {
+    let vtable = this.cast::<*const u8>().read();
+
+    vtable.add(offset).cast::<*const ()>().read()
+}
+
+/// Returns the mangled name of the most derived class of the polymorphic object `this`.
+///
+/// # Safety
+/// `this` must point to a constructed polymorphic object.
+pub unsafe fn type_name(this: *const ()) -> &'static CStr {
+    // The type info is located before the address point of the vtable. The name is the second field
+    // of std::type_info.
+    let vtable = this.cast::<*const *const ()>().read();
+    let info = vtable.sub(1).read();
+    let name = info.cast::<*const c_char>().add(1).read();
+
+    // GCC prefix the name with '*' if it need to be compared by the address.
+    if name.read() == b'*' as c_char {
+        CStr::from_ptr(name.add(1))
+    } else {
+        CStr::from_ptr(name)
+    }
+}